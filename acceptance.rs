@@ -0,0 +1,284 @@
+use ink::primitives::AccountId;
+
+use crate::{
+    AccessControlData, AccessControlError, AccessControlEvents, Role, RoleId, RoleOfferWithdrawn,
+    RoleOffered,
+};
+
+impl<const N: usize, const PAGES: usize> AccessControlData<N, PAGES> {
+    /// set_role_acceptance sets whether granting `role` takes two
+    /// steps. If it does, its admins can't grant it directly, they
+    /// have to offer it with `offer_role` and it's only granted once
+    /// the account accepts it with `accept_role`, so a mistyped account
+    /// can't end up holding it.
+    ///
+    /// Roles requiring acceptance can't be scheduled, and roles with a
+    /// grant delay can't be offered, so a role shouldn't require both.
+    ///
+    /// Like `set_role_admin` it doesn't perform any authorization,
+    /// it's meant to be used while setting up the contract or behind
+    /// the contract's own checks.
+    pub fn set_role_acceptance(&mut self, role: impl Role, required: bool) {
+        let role = Self::index(role) as RoleId;
+
+        match (self.acceptance_roles.contains(role), required) {
+            (false, true) => {
+                self.acceptance_roles.insert(role, &());
+                self.two_step_roles += 1;
+            }
+            (true, false) => {
+                self.acceptance_roles.remove(role);
+                self.two_step_roles -= 1;
+            }
+            _ => {}
+        }
+    }
+
+    /// requires_acceptance returns true if `role` has to be offered
+    /// and accepted to be granted
+    pub fn requires_acceptance(&self, role: impl Role) -> bool {
+        self.two_step_roles > 0
+            && self
+                .acceptance_roles
+                .contains(Self::index(role) as RoleId)
+    }
+
+    /// offer_role offers `role` to `account_id` if `caller` holds the
+    /// admin role of `role`. The role isn't granted until `account_id`
+    /// accepts it, and the admins can withdraw the offer until then.
+    ///
+    /// Offering the role again replaces the previous offer, and
+    /// offering a role the account already holds doesn't do anything.
+    pub fn offer_role<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        account_id: AccountId,
+        role: impl Role,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+        Self::check_role(role)?;
+        self.check_role_admin(caller, role)?;
+        self.check_grant_delay(role)?;
+
+        if self.has_role(account_id, role) {
+            return Ok(());
+        }
+
+        self.role_offers.insert((account_id, role as RoleId), &caller);
+
+        E::emit_role_offered(RoleOffered {
+            role:    role as RoleId,
+            account: account_id,
+            sender:  caller,
+        });
+
+        Ok(())
+    }
+
+    /// accept_role grants `role` to `caller` if it was offered to it.
+    /// The admin that made the offer must still hold the admin role of
+    /// `role`, otherwise it fails with `MissingRole`, and the role must
+    /// still be grantable without a delay, otherwise it fails with
+    /// `DelayTooShort`.
+    ///
+    /// The `RoleGranted` event has the admin as its sender.
+    pub fn accept_role<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        role: impl Role,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+        Self::check_role(role)?;
+        let offered_by = self
+            .role_offer(caller, role)
+            .ok_or(AccessControlError::RoleNotOffered)?;
+        self.check_role_admin(offered_by, role)?;
+        self.check_grant_delay(role)?;
+
+        self.role_offers.remove((caller, role as RoleId));
+        self.set_role_by::<E>(offered_by, caller, role);
+        Ok(())
+    }
+
+    /// withdraw_offer withdraws the offer of `role` to `account_id` if
+    /// `caller` holds the admin role of `role`. It fails with
+    /// `RoleNotOffered` if there's no such offer.
+    pub fn withdraw_offer<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        account_id: AccountId,
+        role: impl Role,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+        Self::check_role(role)?;
+        self.check_role_admin(caller, role)?;
+
+        if self
+            .role_offers
+            .take((account_id, role as RoleId))
+            .is_none()
+        {
+            return Err(AccessControlError::RoleNotOffered);
+        }
+
+        E::emit_role_offer_withdrawn(RoleOfferWithdrawn {
+            role:    role as RoleId,
+            account: account_id,
+            sender:  caller,
+        });
+
+        Ok(())
+    }
+
+    /// role_offer returns the admin that offered `role` to
+    /// `account_id`, or None if there's no offer
+    pub fn role_offer(&self, account_id: AccountId, role: impl Role) -> Option<AccountId> {
+        self.role_offers.get((account_id, Self::index(role) as RoleId))
+    }
+
+    /// check_acceptance fails with `AcceptanceRequired` if `role` can
+    /// only be granted through `offer_role`
+    pub(crate) fn check_acceptance(&self, role: usize) -> Result<(), AccessControlError> {
+        if self.requires_acceptance(role) {
+            return Err(AccessControlError::AcceptanceRequired);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Expiry, DEFAULT_ADMIN_ROLE};
+
+    fn setup() -> (AccessControlData<4>, AccountId, AccountId) {
+        let mut access_control = AccessControlData::<4>::new_enumerable();
+        let (admin, account) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+
+        access_control.set_role::<()>(admin, DEFAULT_ADMIN_ROLE);
+        (access_control, admin, account)
+    }
+
+    #[ink::test]
+    fn offered_roles_are_granted_on_acceptance() {
+        let (mut access_control, admin, account) = setup();
+        let role = 1;
+
+        access_control.set_role_acceptance(role, true);
+        assert!(access_control.requires_acceptance(role));
+        assert!(!access_control.requires_acceptance(2));
+
+        assert_eq!(access_control.offer_role::<()>(admin, account, role), Ok(()));
+        assert_eq!(access_control.role_offer(account, role), Some(admin));
+        assert!(!access_control.has_role(account, role));
+
+        // only the account the role was offered to can accept it
+        assert_eq!(
+            access_control.accept_role::<()>(admin, role),
+            Err(AccessControlError::RoleNotOffered)
+        );
+
+        assert_eq!(access_control.accept_role::<()>(account, role), Ok(()));
+        assert!(access_control.has_role(account, role));
+        assert_eq!(access_control.role_offer(account, role), None);
+        assert_eq!(access_control.get_role_member_count(role), 1);
+
+        assert_eq!(
+            access_control.accept_role::<()>(account, role),
+            Err(AccessControlError::RoleNotOffered)
+        );
+    }
+
+    #[ink::test]
+    fn offers_can_be_withdrawn() {
+        let (mut access_control, admin, account) = setup();
+        let role = 1;
+
+        access_control.offer_role::<()>(admin, account, role).unwrap();
+
+        // only admins can withdraw
+        assert_eq!(
+            access_control.withdraw_offer::<()>(account, account, role),
+            Err(AccessControlError::MissingRole {
+                account,
+                role: DEFAULT_ADMIN_ROLE as RoleId,
+            })
+        );
+
+        assert_eq!(access_control.withdraw_offer::<()>(admin, account, role), Ok(()));
+        assert_eq!(
+            access_control.withdraw_offer::<()>(admin, account, role),
+            Err(AccessControlError::RoleNotOffered)
+        );
+        assert_eq!(
+            access_control.accept_role::<()>(account, role),
+            Err(AccessControlError::RoleNotOffered)
+        );
+    }
+
+    #[ink::test]
+    fn roles_requiring_acceptance_cant_be_granted_directly() {
+        let (mut access_control, admin, account) = setup();
+        let (role, other) = (1, 2);
+        let expiry = Expiry::Block(ink::env::block_number::<ink::env::DefaultEnvironment>() + 1);
+
+        access_control.set_role_acceptance(role, true);
+
+        assert_eq!(
+            access_control.grant_role::<()>(admin, account, role),
+            Err(AccessControlError::AcceptanceRequired)
+        );
+        assert_eq!(
+            access_control.grant_role_until::<()>(admin, account, role, expiry),
+            Err(AccessControlError::AcceptanceRequired)
+        );
+        assert_eq!(
+            access_control.grant_roles::<()>(admin, &[(account, other), (account, role)]),
+            Err(AccessControlError::AcceptanceRequired)
+        );
+        assert_eq!(
+            access_control.schedule_grant::<()>(admin, account, role, 0),
+            Err(AccessControlError::AcceptanceRequired)
+        );
+        assert!(!access_control.has_role(account, other));
+
+        access_control.set_role_acceptance(role, false);
+        assert_eq!(access_control.two_step_roles, 0);
+        assert_eq!(access_control.grant_role::<()>(admin, account, role), Ok(()));
+    }
+
+    #[ink::test]
+    fn offers_of_former_admins_cant_be_accepted() {
+        let (mut access_control, admin, account) = setup();
+        let role = 1;
+
+        access_control.offer_role::<()>(admin, account, role).unwrap();
+        access_control.unset_role::<()>(admin, DEFAULT_ADMIN_ROLE);
+
+        assert_eq!(
+            access_control.accept_role::<()>(account, role),
+            Err(AccessControlError::MissingRole {
+                account: admin,
+                role:    DEFAULT_ADMIN_ROLE as RoleId,
+            })
+        );
+        assert!(!access_control.has_role(account, role));
+    }
+
+    #[ink::test]
+    fn offers_of_delayed_roles_cant_be_accepted() {
+        let (mut access_control, admin, account) = setup();
+        let role = 1;
+
+        access_control.offer_role::<()>(admin, account, role).unwrap();
+        access_control.set_grant_delay(role, 100);
+
+        assert_eq!(
+            access_control.accept_role::<()>(account, role),
+            Err(AccessControlError::DelayTooShort)
+        );
+        assert!(!access_control.has_role(account, role));
+        assert_eq!(access_control.role_offer(account, role), Some(admin));
+    }
+}
@@ -0,0 +1,546 @@
+use ink::{env::DefaultEnvironment, primitives::AccountId};
+
+use crate::{
+    expiry::Timestamp, AccessControlData, AccessControlError, AccessControlEvents,
+    DefaultAdminDelayChangeCancelled, DefaultAdminDelayChangeScheduled,
+    DefaultAdminTransferCancelled, DefaultAdminTransferScheduled, RoleId, DEFAULT_ADMIN_ROLE,
+};
+
+/// DefaultAdminRules is the state of the default admin rules of an
+/// `AccessControlData`, see `enable_default_admin_rules`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct DefaultAdminRules {
+    /// The only account holding `DEFAULT_ADMIN_ROLE`, if any.
+    pub admin: Option<AccountId>,
+
+    /// The delay, in milliseconds, of the transfers of the role.
+    pub delay: Timestamp,
+
+    /// The account the role is being transferred to, and the block
+    /// timestamp from which it can accept it.
+    pub pending_admin: Option<(AccountId, Timestamp)>,
+
+    /// The delay that will replace `delay`, and the block timestamp
+    /// from which it does.
+    pub pending_delay: Option<(Timestamp, Timestamp)>,
+}
+
+impl DefaultAdminRules {
+    /// current_delay returns the delay in effect, which is the pending
+    /// one once it took effect
+    fn current_delay(&self) -> Timestamp {
+        match self.pending_delay {
+            Some((delay, effect_at)) if effect_at <= now() => delay,
+            _ => self.delay,
+        }
+    }
+
+    /// settle_delay replaces the delay with the pending one if it
+    /// already took effect
+    fn settle_delay(&mut self) {
+        if let Some((delay, effect_at)) = self.pending_delay {
+            if effect_at <= now() {
+                self.delay = delay;
+                self.pending_delay = None;
+            }
+        }
+    }
+}
+
+impl<const N: usize, const PAGES: usize> AccessControlData<N, PAGES> {
+    /// enable_default_admin_rules grants `DEFAULT_ADMIN_ROLE` to
+    /// `admin` and makes it the only account that can hold it. From
+    /// then on the role can't be granted, revoked or renounced like the
+    /// rest, it can only change hands through
+    /// `begin_default_admin_transfer`, which takes `delay` milliseconds
+    /// to be accepted. The unauthorized operations, like `set_role`,
+    /// `unset_role` and `set_roles`, panic before writing anything if
+    /// they'd grant or revoke it, while `try_set_role` and
+    /// `try_unset_role` fail with `EnforcedDefaultAdminRules`.
+    ///
+    /// It's meant to be called in the constructor of the contract. The
+    /// accounts already holding the role lose it: with `new_enumerable`
+    /// they're revoked it, emitting `RoleRevoked` through `E`, and
+    /// otherwise, since they can't be listed, `has_role` and the rest
+    /// of queries stop counting it for anyone but `admin`.
+    pub fn enable_default_admin_rules<E: AccessControlEvents>(
+        &mut self,
+        admin: AccountId,
+        delay: Timestamp,
+    ) {
+        if self.enumerable {
+            let holders = self.role_members(DEFAULT_ADMIN_ROLE, 0, u32::MAX);
+
+            for holder in holders.into_iter().filter(|holder| *holder != admin) {
+                self.unset_role_by::<E>(Self::caller(), holder, DEFAULT_ADMIN_ROLE);
+            }
+        }
+
+        self.default_admin_rules = Some(DefaultAdminRules {
+            admin: None,
+            delay,
+            pending_admin: None,
+            pending_delay: None,
+        });
+
+        self.set_role_by::<E>(Self::caller(), admin, DEFAULT_ADMIN_ROLE);
+    }
+
+    /// default_admin returns the only account holding
+    /// `DEFAULT_ADMIN_ROLE` under the default admin rules, or None if
+    /// nobody does or the rules aren't enabled
+    pub fn default_admin(&self) -> Option<AccountId> {
+        self.default_admin_rules.and_then(|rules| rules.admin)
+    }
+
+    /// default_admin_delay returns the delay, in milliseconds, of the
+    /// transfers of `DEFAULT_ADMIN_ROLE`
+    pub fn default_admin_delay(&self) -> Timestamp {
+        self.default_admin_rules
+            .map_or(0, |rules| rules.current_delay())
+    }
+
+    /// pending_default_admin returns the account `DEFAULT_ADMIN_ROLE` is
+    /// being transferred to and the block timestamp from which it can
+    /// accept it, if there's a transfer in progress
+    pub fn pending_default_admin(&self) -> Option<(AccountId, Timestamp)> {
+        self.default_admin_rules
+            .and_then(|rules| rules.pending_admin)
+    }
+
+    /// pending_default_admin_delay returns the delay that will replace
+    /// the current one and the block timestamp from which it does, if
+    /// there's a change that didn't take effect yet
+    pub fn pending_default_admin_delay(&self) -> Option<(Timestamp, Timestamp)> {
+        self.default_admin_rules
+            .and_then(|rules| rules.pending_delay)
+            .filter(|(_, effect_at)| *effect_at > now())
+    }
+
+    /// begin_default_admin_transfer starts transferring
+    /// `DEFAULT_ADMIN_ROLE` to `new_admin` if `caller` holds it.
+    /// `new_admin` can accept it once the current delay passes, until
+    /// then the admin can cancel it. Beginning another transfer
+    /// replaces the pending one.
+    pub fn begin_default_admin_transfer<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        new_admin: AccountId,
+    ) -> Result<(), AccessControlError> {
+        self.ensure_default_admin(caller)?;
+
+        let ready_at = now().saturating_add(self.default_admin_delay());
+        self.rules_mut().pending_admin = Some((new_admin, ready_at));
+
+        E::emit_default_admin_transfer_scheduled(DefaultAdminTransferScheduled {
+            new_admin,
+            ready_at,
+        });
+
+        Ok(())
+    }
+
+    /// cancel_default_admin_transfer cancels the transfer of
+    /// `DEFAULT_ADMIN_ROLE` in progress, if any, if `caller` holds it
+    pub fn cancel_default_admin_transfer<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+    ) -> Result<(), AccessControlError> {
+        self.ensure_default_admin(caller)?;
+
+        if let Some((new_admin, _)) = self.rules_mut().pending_admin.take() {
+            E::emit_default_admin_transfer_cancelled(DefaultAdminTransferCancelled { new_admin });
+        }
+
+        Ok(())
+    }
+
+    /// accept_default_admin_transfer completes the transfer of
+    /// `DEFAULT_ADMIN_ROLE` to `caller`, revoking it from the current
+    /// admin. It fails with `GrantNotPending` if the role isn't being
+    /// transferred to `caller`, with `GrantNotReady` if the delay
+    /// didn't pass yet, and with `AccountFrozen` if `caller` is frozen.
+    pub fn accept_default_admin_transfer<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+    ) -> Result<(), AccessControlError> {
+        if self.is_frozen(caller) {
+            return Err(AccessControlError::AccountFrozen { account: caller });
+        }
+
+        let ready_at = match self.pending_default_admin() {
+            Some((new_admin, ready_at)) if new_admin == caller => ready_at,
+            _ => return Err(AccessControlError::GrantNotPending),
+        };
+
+        if ready_at > now() {
+            return Err(AccessControlError::GrantNotReady);
+        }
+
+        self.rules_mut().pending_admin = None;
+
+        if let Some(admin) = self.default_admin() {
+            self.unset_role_by::<E>(caller, admin, DEFAULT_ADMIN_ROLE);
+        }
+        self.set_role_by::<E>(caller, caller, DEFAULT_ADMIN_ROLE);
+
+        Ok(())
+    }
+
+    /// change_default_admin_delay schedules replacing the delay of the
+    /// transfers of `DEFAULT_ADMIN_ROLE` with `new_delay` if `caller`
+    /// holds it. The change takes effect once the current delay passes,
+    /// so that shortening it can't speed up a transfer, and until then
+    /// the admin can cancel it. Transfers already in progress keep
+    /// their schedule.
+    pub fn change_default_admin_delay<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        new_delay: Timestamp,
+    ) -> Result<(), AccessControlError> {
+        self.ensure_default_admin(caller)?;
+
+        let rules = self.rules_mut();
+        rules.settle_delay();
+
+        let effect_at = now().saturating_add(rules.delay);
+        rules.pending_delay = Some((new_delay, effect_at));
+
+        E::emit_default_admin_delay_change_scheduled(DefaultAdminDelayChangeScheduled {
+            new_delay,
+            effect_at,
+        });
+
+        Ok(())
+    }
+
+    /// cancel_default_admin_delay_change cancels the change of the
+    /// delay of the transfers of `DEFAULT_ADMIN_ROLE` that didn't take
+    /// effect yet, if any, if `caller` holds it
+    pub fn cancel_default_admin_delay_change<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+    ) -> Result<(), AccessControlError> {
+        self.ensure_default_admin(caller)?;
+
+        let rules = self.rules_mut();
+        rules.settle_delay();
+
+        if let Some((new_delay, _)) = rules.pending_delay.take() {
+            E::emit_default_admin_delay_change_cancelled(DefaultAdminDelayChangeCancelled {
+                new_delay,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// check_default_admin_rules fails with `EnforcedDefaultAdminRules`
+    /// if `role` is `DEFAULT_ADMIN_ROLE` and the default admin rules
+    /// are enabled, since it can only change hands through a transfer
+    pub(crate) fn check_default_admin_rules(&self, role: usize) -> Result<(), AccessControlError> {
+        if role == DEFAULT_ADMIN_ROLE && self.default_admin_rules.is_some() {
+            return Err(AccessControlError::EnforcedDefaultAdminRules);
+        }
+
+        Ok(())
+    }
+
+    /// assert_default_admin_rules panics if `role` is
+    /// `DEFAULT_ADMIN_ROLE` and the default admin rules are enabled. It
+    /// guards the unauthorized operations, which would otherwise grant
+    /// or revoke it outside of a transfer.
+    pub(crate) fn assert_default_admin_rules(&self, role: usize) {
+        assert!(
+            self.check_default_admin_rules(role).is_ok(),
+            "the default admin role can only change hands through a transfer"
+        );
+    }
+
+    /// check_default_admin_grant fails with `EnforcedDefaultAdminRules`
+    /// if granting `role` to `account_id` would make it a second holder
+    /// of `DEFAULT_ADMIN_ROLE` under the default admin rules
+    pub(crate) fn check_default_admin_grant(
+        &self,
+        account_id: AccountId,
+        role: usize,
+    ) -> Result<(), AccessControlError> {
+        let other_admin = self.default_admin().is_some_and(|admin| admin != account_id);
+
+        if role == DEFAULT_ADMIN_ROLE && other_admin {
+            return Err(AccessControlError::EnforcedDefaultAdminRules);
+        }
+
+        Ok(())
+    }
+
+    /// is_displaced_admin returns true if `role` is `DEFAULT_ADMIN_ROLE`
+    /// and `account_id` isn't the default admin under the default admin
+    /// rules, like the accounts that held it before they were enabled
+    pub(crate) fn is_displaced_admin(&self, account_id: AccountId, role: usize) -> bool {
+        role == DEFAULT_ADMIN_ROLE
+            && self
+                .default_admin_rules
+                .is_some_and(|rules| rules.admin != Some(account_id))
+    }
+
+    /// default_admin_changed keeps track of the only holder of
+    /// `DEFAULT_ADMIN_ROLE` under the default admin rules, panicking if
+    /// it's granted to another account while somebody holds it
+    pub(crate) fn default_admin_changed(&mut self, account_id: AccountId, granted: bool) {
+        let Some(rules) = self.default_admin_rules.as_mut() else {
+            return;
+        };
+
+        match (rules.admin, granted) {
+            (None, true) => rules.admin = Some(account_id),
+            (Some(admin), true) => assert!(
+                admin == account_id,
+                "only one account can hold the default admin role"
+            ),
+            (Some(admin), false) if admin == account_id => rules.admin = None,
+            _ => {}
+        }
+    }
+
+    /// ensure_default_admin fails with `MissingRole` unless
+    /// `account_id` is the default admin and, like for any other role,
+    /// `has_role` says it holds it, so frozen admins can't use it
+    fn ensure_default_admin(&self, account_id: AccountId) -> Result<(), AccessControlError> {
+        if self.default_admin().is_some_and(|admin| admin == account_id) {
+            return self.ensure_role(account_id, DEFAULT_ADMIN_ROLE);
+        }
+
+        Err(AccessControlError::MissingRole {
+            account: account_id,
+            role:    DEFAULT_ADMIN_ROLE as RoleId,
+        })
+    }
+
+    fn rules_mut(&mut self) -> &mut DefaultAdminRules {
+        self.default_admin_rules
+            .as_mut()
+            .expect("the default admin rules aren't enabled")
+    }
+}
+
+fn now() -> Timestamp {
+    ink::env::block_timestamp::<DefaultEnvironment>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink::env::test::set_block_timestamp;
+
+    fn setup() -> (AccessControlData<4>, AccountId, AccountId) {
+        let mut access_control = AccessControlData::<4>::new();
+        let (admin, other) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+
+        set_block_timestamp::<DefaultEnvironment>(1_000);
+        access_control.enable_default_admin_rules::<()>(admin, 500);
+
+        (access_control, admin, other)
+    }
+
+    #[ink::test]
+    fn default_admin_cant_be_granted_or_revoked() {
+        let (mut access_control, admin, other) = setup();
+
+        assert_eq!(access_control.default_admin(), Some(admin));
+        assert!(access_control.has_role(admin, DEFAULT_ADMIN_ROLE));
+
+        for res in [
+            access_control.grant_role::<()>(admin, other, DEFAULT_ADMIN_ROLE),
+            access_control.grant_roles::<()>(admin, &[(other, DEFAULT_ADMIN_ROLE)]),
+            access_control.schedule_grant::<()>(admin, other, DEFAULT_ADMIN_ROLE, 500),
+            access_control.offer_role::<()>(admin, other, DEFAULT_ADMIN_ROLE),
+            access_control.revoke_role::<()>(admin, admin, DEFAULT_ADMIN_ROLE),
+            access_control.renounce_role::<()>(admin, admin, DEFAULT_ADMIN_ROLE),
+        ] {
+            assert_eq!(res, Err(AccessControlError::EnforcedDefaultAdminRules));
+        }
+
+        // the rest of roles work as usual
+        assert_eq!(access_control.grant_role::<()>(admin, other, 1), Ok(()));
+        assert!(!access_control.has_role(other, DEFAULT_ADMIN_ROLE));
+        assert!(access_control.has_role(admin, DEFAULT_ADMIN_ROLE));
+    }
+
+    #[ink::test]
+    fn try_set_role_cant_change_the_default_admin() {
+        let (mut access_control, admin, other) = setup();
+        let enforced = Err(AccessControlError::EnforcedDefaultAdminRules);
+
+        assert_eq!(access_control.try_set_role::<()>(other, DEFAULT_ADMIN_ROLE), enforced);
+        assert_eq!(access_control.try_unset_role::<()>(admin, DEFAULT_ADMIN_ROLE), enforced);
+        assert!(!access_control.roles_per_account.contains((other, 0)));
+        assert_eq!(access_control.default_admin(), Some(admin));
+        assert_eq!(access_control.try_set_role::<()>(admin, DEFAULT_ADMIN_ROLE), enforced);
+    }
+
+    #[ink::test]
+    #[should_panic(expected = "the default admin role can only change hands through a transfer")]
+    fn set_role_cant_grant_the_default_admin_role() {
+        let (mut access_control, _, other) = setup();
+
+        access_control.set_role::<()>(other, DEFAULT_ADMIN_ROLE);
+    }
+
+    #[ink::test]
+    #[should_panic(expected = "the default admin role can only change hands through a transfer")]
+    fn unset_role_cant_revoke_the_default_admin_role() {
+        let (mut access_control, admin, _) = setup();
+
+        access_control.unset_role::<()>(admin, DEFAULT_ADMIN_ROLE);
+    }
+
+    #[ink::test]
+    #[should_panic(expected = "the default admin role can only change hands through a transfer")]
+    fn set_roles_cant_grant_the_default_admin_role() {
+        let (mut access_control, _, other) = setup();
+
+        access_control.set_roles::<()>(other, &crate::RoleMask::of(&[DEFAULT_ADMIN_ROLE]));
+    }
+
+    #[ink::test]
+    #[should_panic(expected = "the default admin role can only change hands through a transfer")]
+    fn set_account_roles_cant_grant_the_default_admin_role() {
+        let (mut access_control, _, other) = setup();
+
+        access_control.set_account_roles::<()>(other, &[1, DEFAULT_ADMIN_ROLE]);
+    }
+
+    #[ink::test]
+    fn enabling_the_rules_displaces_previous_admins() {
+        let (admin, old_admin) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+
+        let mut access_control = AccessControlData::<4>::new();
+        access_control.set_role::<()>(old_admin, DEFAULT_ADMIN_ROLE);
+        access_control.enable_default_admin_rules::<()>(admin, 500);
+
+        assert!(!access_control.has_role(old_admin, DEFAULT_ADMIN_ROLE));
+        assert!(!access_control.has_any_role(old_admin, &[DEFAULT_ADMIN_ROLE]));
+        assert!(access_control.roles_of(old_admin).is_empty());
+        assert!(access_control.has_role(admin, DEFAULT_ADMIN_ROLE));
+        assert_eq!(
+            access_control.begin_default_admin_transfer::<()>(old_admin, old_admin),
+            Err(AccessControlError::MissingRole {
+                account: old_admin,
+                role:    DEFAULT_ADMIN_ROLE as RoleId,
+            })
+        );
+    }
+
+    #[ink::test]
+    fn displaced_admins_can_be_transferred_the_role() {
+        let (admin, old_admin) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+
+        let mut access_control = AccessControlData::<4>::new();
+        set_block_timestamp::<DefaultEnvironment>(1_000);
+        access_control.set_role::<()>(old_admin, DEFAULT_ADMIN_ROLE);
+        access_control.enable_default_admin_rules::<()>(admin, 500);
+
+        access_control.begin_default_admin_transfer::<()>(admin, old_admin).unwrap();
+        set_block_timestamp::<DefaultEnvironment>(1_500);
+        assert_eq!(access_control.accept_default_admin_transfer::<()>(old_admin), Ok(()));
+
+        assert_eq!(access_control.default_admin(), Some(old_admin));
+        assert!(access_control.has_role(old_admin, DEFAULT_ADMIN_ROLE));
+        assert!(!access_control.has_role(admin, DEFAULT_ADMIN_ROLE));
+        assert_eq!(
+            access_control.begin_default_admin_transfer::<()>(old_admin, admin),
+            Ok(())
+        );
+    }
+
+    #[ink::test]
+    fn enabling_the_rules_revokes_indexed_admins() {
+        let (admin, old_admin) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+
+        let mut access_control = AccessControlData::<4>::new_enumerable();
+        access_control.set_role::<()>(old_admin, DEFAULT_ADMIN_ROLE);
+        access_control.enable_default_admin_rules::<()>(admin, 500);
+
+        assert!(!access_control.roles_per_account.contains((old_admin, 0)));
+        assert_eq!(access_control.role_members(DEFAULT_ADMIN_ROLE, 0, 10), [admin]);
+    }
+
+    #[ink::test]
+    fn default_admin_transfers_are_delayed() {
+        let (mut access_control, admin, other) = setup();
+
+        assert_eq!(
+            access_control.begin_default_admin_transfer::<()>(other, other),
+            Err(AccessControlError::MissingRole {
+                account: other,
+                role:    DEFAULT_ADMIN_ROLE as RoleId,
+            })
+        );
+
+        access_control.begin_default_admin_transfer::<()>(admin, other).unwrap();
+        assert_eq!(access_control.pending_default_admin(), Some((other, 1_500)));
+        assert_eq!(
+            access_control.accept_default_admin_transfer::<()>(other),
+            Err(AccessControlError::GrantNotReady)
+        );
+
+        set_block_timestamp::<DefaultEnvironment>(1_500);
+        assert_eq!(
+            access_control.accept_default_admin_transfer::<()>(admin),
+            Err(AccessControlError::GrantNotPending)
+        );
+        assert_eq!(access_control.accept_default_admin_transfer::<()>(other), Ok(()));
+
+        assert_eq!(access_control.default_admin(), Some(other));
+        assert_eq!(access_control.pending_default_admin(), None);
+        assert!(access_control.has_role(other, DEFAULT_ADMIN_ROLE));
+        assert!(!access_control.has_role(admin, DEFAULT_ADMIN_ROLE));
+    }
+
+    #[ink::test]
+    fn default_admin_transfers_can_be_cancelled() {
+        let (mut access_control, admin, other) = setup();
+
+        access_control.begin_default_admin_transfer::<()>(admin, other).unwrap();
+        assert_eq!(access_control.cancel_default_admin_transfer::<()>(admin), Ok(()));
+
+        set_block_timestamp::<DefaultEnvironment>(1_500);
+        assert_eq!(
+            access_control.accept_default_admin_transfer::<()>(other),
+            Err(AccessControlError::GrantNotPending)
+        );
+        assert_eq!(access_control.default_admin(), Some(admin));
+    }
+
+    #[ink::test]
+    fn default_admin_delay_changes_are_delayed() {
+        let (mut access_control, admin, other) = setup();
+
+        access_control.change_default_admin_delay::<()>(admin, 100).unwrap();
+        assert_eq!(access_control.default_admin_delay(), 500);
+        assert_eq!(access_control.pending_default_admin_delay(), Some((100, 1_500)));
+
+        // transfers begun before the change takes effect use the old delay
+        access_control.begin_default_admin_transfer::<()>(admin, other).unwrap();
+        assert_eq!(access_control.pending_default_admin(), Some((other, 1_500)));
+
+        set_block_timestamp::<DefaultEnvironment>(1_500);
+        assert_eq!(access_control.default_admin_delay(), 100);
+        assert_eq!(access_control.pending_default_admin_delay(), None);
+
+        // once in effect it can't be cancelled anymore
+        access_control.cancel_default_admin_delay_change::<()>(admin).unwrap();
+        assert_eq!(access_control.default_admin_delay(), 100);
+
+        access_control.change_default_admin_delay::<()>(admin, 1_000).unwrap();
+        assert_eq!(access_control.pending_default_admin_delay(), Some((1_000, 1_600)));
+        access_control.cancel_default_admin_delay_change::<()>(admin).unwrap();
+        assert_eq!(access_control.pending_default_admin_delay(), None);
+        assert_eq!(access_control.default_admin_delay(), 100);
+    }
+}
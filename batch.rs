@@ -0,0 +1,419 @@
+use ink::{prelude::vec::Vec, primitives::AccountId};
+
+use crate::{AccessControlData, AccessControlError, AccessControlEvents, BitMap, Role, RoleMask};
+
+impl<const N: usize, const PAGES: usize> AccessControlData<N, PAGES> {
+    /// set_role_batch grants the role of every `(account, role)` pair
+    /// of `roles` without any authorization, like `set_role`.
+    ///
+    /// Each page of an account is read and written once, however many
+    /// of its roles are in the batch.
+    pub fn set_role_batch<E: AccessControlEvents>(&mut self, roles: &[(AccountId, impl Role)]) {
+        let roles = roles.iter().map(|(account_id, role)| (*account_id, Self::index(*role)));
+        self.apply_roles::<E>(Self::caller(), roles, true);
+    }
+
+    /// unset_role_batch revokes the role of every `(account, role)`
+    /// pair of `roles` without any authorization, like `unset_role`
+    pub fn unset_role_batch<E: AccessControlEvents>(
+        &mut self,
+        roles: &[(AccountId, impl Role)],
+    ) {
+        let roles = roles.iter().map(|(account_id, role)| (*account_id, Self::index(*role)));
+        self.apply_roles::<E>(Self::caller(), roles, false);
+    }
+
+    /// set_account_roles grants all of `roles` to `account_id` without
+    /// any authorization, writing each of its pages once
+    pub fn set_account_roles<E: AccessControlEvents>(
+        &mut self,
+        account_id: AccountId,
+        roles: &[impl Role],
+    ) {
+        let roles = roles.iter().map(|role| (account_id, Self::index(*role)));
+        self.apply_roles::<E>(Self::caller(), roles, true);
+    }
+
+    /// unset_account_roles revokes all of `roles` from `account_id`
+    /// without any authorization, writing each of its pages once
+    pub fn unset_account_roles<E: AccessControlEvents>(
+        &mut self,
+        account_id: AccountId,
+        roles: &[impl Role],
+    ) {
+        let roles = roles.iter().map(|role| (account_id, Self::index(*role)));
+        self.apply_roles::<E>(Self::caller(), roles, false);
+    }
+
+    /// set_roles replaces the roles of `account_id` with the ones in
+    /// `mask` without any authorization, emitting `RoleGranted` and
+    /// `RoleRevoked` for every role that changed
+    pub fn set_roles<E: AccessControlEvents>(
+        &mut self,
+        account_id: AccountId,
+        mask: &RoleMask<N, PAGES>,
+    ) {
+        let diff = self.diff_roles(account_id, mask);
+        self.apply_diff::<E>(Self::caller(), account_id, diff);
+    }
+
+    /// grant_roles grants the role of every `(account, role)` pair of
+    /// `roles` if `caller` holds the admin role of all of them. If any
+    /// check fails nothing is granted.
+    pub fn grant_roles<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        roles: &[(AccountId, impl Role)],
+    ) -> Result<(), AccessControlError> {
+        self.check_batch(caller, roles)?;
+        roles
+            .iter()
+            .try_for_each(|(_, role)| self.check_direct_grant(Self::index(*role)))?;
+
+        let roles = roles.iter().map(|(account_id, role)| (*account_id, Self::index(*role)));
+        self.apply_roles::<E>(caller, roles, true);
+        Ok(())
+    }
+
+    /// revoke_roles revokes the role of every `(account, role)` pair of
+    /// `roles` if `caller` holds the admin role of all of them. If any
+    /// check fails nothing is revoked.
+    pub fn revoke_roles<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        roles: &[(AccountId, impl Role)],
+    ) -> Result<(), AccessControlError> {
+        self.check_batch(caller, roles)?;
+
+        let roles = roles.iter().map(|(account_id, role)| (*account_id, Self::index(*role)));
+        self.apply_roles::<E>(caller, roles, false);
+        Ok(())
+    }
+
+    /// replace_roles replaces the roles of `account_id` with the ones
+    /// in `mask` if `caller` holds the admin role of every role that
+    /// would be granted or revoked. If any check fails nothing changes.
+    pub fn replace_roles<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        account_id: AccountId,
+        mask: &RoleMask<N, PAGES>,
+    ) -> Result<(), AccessControlError> {
+        let diff = self.diff_roles(account_id, mask);
+
+        for (page, stored, active, new) in &diff {
+            let granted = new.difference(active);
+
+            for bit in stored.difference(new).union(&granted).iter_ones() {
+                self.check_role_admin(caller, *page as usize * N * 8 + bit)?;
+            }
+
+            for bit in granted.iter_ones() {
+                self.check_direct_grant(*page as usize * N * 8 + bit)?;
+            }
+        }
+
+        self.apply_diff::<E>(caller, account_id, diff);
+        Ok(())
+    }
+
+    fn check_batch(
+        &self,
+        caller: AccountId,
+        roles: &[(AccountId, impl Role)],
+    ) -> Result<(), AccessControlError> {
+        roles.iter().try_for_each(|(_, role)| {
+            let role = Self::index(*role);
+            Self::check_role(role)?;
+            self.check_role_admin(caller, role)
+        })
+    }
+
+    /// apply_roles grants or revokes every `(account, role)` pair of
+    /// `roles`, reading each page they touch once and writing back
+    /// only the ones that changed. `DEFAULT_ADMIN_ROLE` under the
+    /// default admin rules panics before anything is written.
+    fn apply_roles<E: AccessControlEvents>(
+        &mut self,
+        sender: AccountId,
+        roles: impl Iterator<Item = (AccountId, usize)> + Clone,
+        grant: bool,
+    ) {
+        for (_, role) in roles.clone() {
+            self.assert_default_admin_rules(role);
+        }
+
+        let mut pages: Vec<(AccountId, u32, BitMap<N>, bool)> = Vec::new();
+
+        for (account_id, role) in roles {
+            let (page, bit) = Self::page_of(role);
+            let index = match pages
+                .iter()
+                .position(|(account, p, ..)| *account == account_id && *p == page)
+            {
+                Some(index) => index,
+                None => {
+                    let account_roles = self.clear_stale(account_id, page);
+                    pages.push((account_id, page, account_roles, false));
+                    pages.len() - 1
+                }
+            };
+
+            let (_, _, account_roles, changed) = &mut pages[index];
+            if account_roles.has_bit_set(bit) == grant {
+                if grant {
+                    self.regrant::<E>(sender, account_id, role);
+                }
+                continue;
+            }
+
+            if grant {
+                account_roles.set_bit(bit);
+            } else {
+                account_roles.clear_bit(bit);
+            }
+            *changed = true;
+
+            self.role_changed::<E>(sender, account_id, role, grant);
+        }
+
+        for (account_id, page, account_roles, changed) in pages {
+            if changed {
+                self.store_page(account_id, page, &account_roles);
+            }
+        }
+    }
+
+    /// diff_roles returns the pages of the roles of `account_id` that
+    /// differ from the ones of `mask`, as `(page, stored, active, new)`,
+    /// where `stored` are the roles in its bitmap and `active` the ones
+    /// it holds, without the expired and the scheduled ones. Stale
+    /// roles aren't part of either.
+    fn diff_roles(
+        &self,
+        account_id: AccountId,
+        mask: &RoleMask<N, PAGES>,
+    ) -> Vec<(u32, BitMap<N>, BitMap<N>, BitMap<N>)> {
+        mask.pages
+            .iter()
+            .enumerate()
+            .filter_map(|(page, new)| {
+                let page = page as u32;
+                let stored = self
+                    .roles_per_account
+                    .get((account_id, page))
+                    .unwrap_or_default();
+                let stored = stored.difference(&self.stale_roles(account_id, page, &stored));
+
+                let mut active = stored;
+                if self.may_be_inactive() {
+                    for bit in stored.iter_ones() {
+                        if self.is_inactive(account_id, page as usize * N * 8 + bit) {
+                            active.clear_bit(bit);
+                        }
+                    }
+                }
+
+                (stored != *new || active != *new).then_some((page, stored, active, *new))
+            })
+            .collect()
+    }
+
+    /// apply_diff writes the pages of `diff`, revoking the stored roles
+    /// that aren't in `new` and granting the ones in `new` that aren't
+    /// active. The expired and scheduled ones are granted again like
+    /// `set_role` does, making them permanent right away.
+    fn apply_diff<E: AccessControlEvents>(
+        &mut self,
+        sender: AccountId,
+        account_id: AccountId,
+        diff: Vec<(u32, BitMap<N>, BitMap<N>, BitMap<N>)>,
+    ) {
+        for (page, stored, active, new) in diff {
+            // changing the default admin panics before the page is written
+            for bit in stored.difference(&new).union(&new.difference(&active)).iter_ones() {
+                self.assert_default_admin_rules(page as usize * N * 8 + bit);
+            }
+
+            self.clear_stale(account_id, page);
+            self.store_page(account_id, page, &new);
+
+            for bit in stored.difference(&new).iter_ones() {
+                self.role_changed::<E>(sender, account_id, page as usize * N * 8 + bit, false);
+            }
+
+            for bit in new.difference(&active).iter_ones() {
+                let role = page as usize * N * 8 + bit;
+
+                if stored.has_bit_set(bit) {
+                    self.regrant::<E>(sender, account_id, role);
+                } else {
+                    self.role_changed::<E>(sender, account_id, role, true);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Expiry, DEFAULT_ADMIN_ROLE};
+    use ink::env::{test::set_block_timestamp, DefaultEnvironment};
+
+    #[ink::test]
+    fn set_role_batch_works() {
+        let mut access_control = AccessControlData::<1, 2>::new_enumerable();
+        let (a, b) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+
+        access_control.set_role_batch::<()>(&[(a, 1), (b, 1), (a, 9), (a, 2)]);
+
+        assert!(access_control.has_role(a, 1));
+        assert!(access_control.has_role(a, 2));
+        assert!(access_control.has_role(a, 9));
+        assert!(access_control.has_role(b, 1));
+        assert_eq!(access_control.get_role_member_count(1), 2);
+
+        access_control.unset_role_batch::<()>(&[(a, 1), (a, 2), (b, 1)]);
+
+        assert!(!access_control.has_role(a, 1));
+        assert!(!access_control.has_role(b, 1));
+        assert!(access_control.has_role(a, 9));
+        assert_eq!(access_control.get_role_member_count(1), 0);
+
+        // the emptied pages are removed
+        assert!(!access_control.roles_per_account.contains((a, 0)));
+        assert!(!access_control.roles_per_account.contains((b, 0)));
+    }
+
+    #[ink::test]
+    fn set_account_roles_works() {
+        let mut access_control = AccessControlData::<4>::new();
+        let account = AccountId::from([1u8; 32]);
+
+        access_control.set_account_roles::<()>(account, &[1, 2, 3]);
+        assert!(access_control.has_role(account, 1));
+        assert!(access_control.has_role(account, 3));
+
+        access_control.unset_account_roles::<()>(account, &[1, 3]);
+        assert!(!access_control.has_role(account, 1));
+        assert!(access_control.has_role(account, 2));
+        assert!(!access_control.has_role(account, 3));
+    }
+
+    #[ink::test]
+    fn set_roles_replaces_every_role() {
+        let mut access_control = AccessControlData::<1, 2>::new_enumerable();
+        let account = AccountId::from([1u8; 32]);
+
+        access_control.set_account_roles::<()>(account, &[1, 2, 9]);
+        access_control.set_roles::<()>(account, &RoleMask::from_roles(&[2, 3]));
+
+        assert!(!access_control.has_role(account, 1));
+        assert!(access_control.has_role(account, 2));
+        assert!(access_control.has_role(account, 3));
+        assert!(!access_control.has_role(account, 9));
+        assert_eq!(access_control.get_role_member_count(9), 0);
+        assert!(!access_control.roles_per_account.contains((account, 1)));
+
+        access_control.set_roles::<()>(account, &RoleMask::new());
+        assert!(!access_control.roles_per_account.contains((account, 0)));
+    }
+
+    #[ink::test]
+    fn replace_roles_grants_inactive_roles_again() {
+        let mut access_control = AccessControlData::<4>::new_enumerable();
+        let (admin, account) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+
+        set_block_timestamp::<DefaultEnvironment>(1_000);
+        access_control.set_role::<()>(admin, DEFAULT_ADMIN_ROLE);
+        access_control.set_role_until::<()>(account, 1, Expiry::Timestamp(1_500));
+        access_control.schedule_grant::<()>(admin, account, 2, 1_000).unwrap();
+        access_control.schedule_grant::<()>(admin, account, 3, 1_000).unwrap();
+        set_block_timestamp::<DefaultEnvironment>(1_500);
+
+        // the expired and the scheduled roles aren't held yet
+        assert_eq!(
+            access_control.replace_roles::<()>(admin, account, &RoleMask::of(&[1, 2])),
+            Ok(())
+        );
+
+        assert!(access_control.has_role(account, 1));
+        assert!(access_control.has_role(account, 2));
+        assert!(!access_control.has_role(account, 3));
+        assert_eq!(access_control.role_expiry(account, 1), None);
+        assert_eq!(access_control.scheduled_grant(account, 2), None);
+        assert_eq!(access_control.scheduled_grant(account, 3), None);
+        assert_eq!(access_control.role_members(1, 0, 10), [account]);
+        assert_eq!(access_control.role_members(2, 0, 10), [account]);
+        assert_eq!(access_control.pending_count, 0);
+        assert_eq!(access_control.timed_grants, 0);
+    }
+
+    #[ink::test]
+    fn grant_and_revoke_roles_are_all_or_nothing() {
+        let mut access_control = AccessControlData::<4>::new();
+        let (admin, a, b) = (
+            AccountId::from([1u8; 32]),
+            AccountId::from([2u8; 32]),
+            AccountId::from([3u8; 32]),
+        );
+        let (role, managed_role, manager_role) = (1, 2, 3);
+
+        access_control.set_role::<()>(admin, DEFAULT_ADMIN_ROLE);
+        access_control.set_role_admin::<()>(managed_role, manager_role);
+
+        // the admin doesn't administer `managed_role`, so nothing is
+        // granted
+        assert_eq!(
+            access_control.grant_roles::<()>(admin, &[(a, role), (b, managed_role)]),
+            Err(AccessControlError::MissingRole {
+                account: admin,
+                role:    manager_role as u32,
+            })
+        );
+        assert!(!access_control.has_role(a, role));
+
+        // neither when a role is out of range
+        assert_eq!(
+            access_control.grant_roles::<()>(admin, &[(a, role), (b, 32)]),
+            Err(AccessControlError::RoleOutOfRange)
+        );
+        assert!(!access_control.has_role(a, role));
+
+        assert_eq!(access_control.grant_roles::<()>(admin, &[(a, role), (b, role)]), Ok(()));
+        assert!(access_control.has_role(a, role));
+        assert!(access_control.has_role(b, role));
+
+        assert_eq!(access_control.revoke_roles::<()>(admin, &[(a, role), (b, role)]), Ok(()));
+        assert!(!access_control.has_role(a, role));
+        assert!(!access_control.has_role(b, role));
+    }
+
+    #[ink::test]
+    fn replace_roles_checks_every_changed_role() {
+        let mut access_control = AccessControlData::<4>::new();
+        let (admin, account) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+        let (role, managed_role, manager_role) = (1, 2, 3);
+
+        access_control.set_role::<()>(admin, DEFAULT_ADMIN_ROLE);
+        access_control.set_role_admin::<()>(managed_role, manager_role);
+        access_control.set_role::<()>(account, managed_role);
+
+        // revoking `managed_role` needs `manager_role`
+        assert_eq!(
+            access_control.replace_roles::<()>(admin, account, &RoleMask::from_roles(&[role])),
+            Err(AccessControlError::MissingRole {
+                account: admin,
+                role:    manager_role as u32,
+            })
+        );
+        assert!(!access_control.has_role(account, role));
+
+        // but keeping it doesn't
+        let mask = RoleMask::from_roles(&[role, managed_role]);
+        assert_eq!(access_control.replace_roles::<()>(admin, account, &mask), Ok(()));
+        assert!(access_control.has_role(account, role));
+        assert!(access_control.has_role(account, managed_role));
+    }
+}
@@ -0,0 +1,180 @@
+use ink::{prelude::vec::Vec, primitives::AccountId};
+
+use crate::{AccessControlData, Role, RoleId};
+
+impl<const N: usize, const PAGES: usize> AccessControlData<N, PAGES> {
+    /// get_role_member_count returns the number of accounts holding
+    /// `role`. It's always 0 unless the data was created with
+    /// `new_enumerable`.
+    pub fn get_role_member_count(&self, role: impl Role) -> u32 {
+        self.role_member_counts.get(role.index() as RoleId).unwrap_or(0)
+    }
+
+    /// get_role_member returns the account at `index` of the members
+    /// of `role`, with `index` going from 0 to
+    /// `get_role_member_count(role)`.
+    ///
+    /// The order of the members isn't stable: revoking a role moves
+    /// the last member of it to the position of the revoked one.
+    pub fn get_role_member(&self, role: impl Role, index: u32) -> Option<AccountId> {
+        // revoke_role_from_all leaves the old members past the count
+        if index >= self.get_role_member_count(role) {
+            return None;
+        }
+
+        self.role_members.get((role.index() as RoleId, index))
+    }
+
+    /// role_members returns up to `limit` members of `role`, starting
+    /// at `offset`. Each member is a storage read, so `limit` should be
+    /// kept small enough for the call to fit in a block.
+    pub fn role_members(&self, role: impl Role, offset: u32, limit: u32) -> Vec<AccountId> {
+        let end = offset
+            .saturating_add(limit)
+            .min(self.get_role_member_count(role));
+
+        (offset..end)
+            .filter_map(|index| self.get_role_member(role, index))
+            .collect()
+    }
+
+    pub(crate) fn add_role_member(&mut self, role: RoleId, account_id: AccountId) {
+        let count = self.role_member_counts.get(role).unwrap_or(0);
+
+        self.role_members.insert((role, count), &account_id);
+        self.role_member_positions.insert((role, account_id), &count);
+        self.role_member_counts.insert(role, &(count + 1));
+    }
+
+    /// forget_stale_member removes `account_id` from the members of a
+    /// previous generation of `role`. Its slot is removed too, unless
+    /// a member of the current generation has taken it since.
+    pub(crate) fn forget_stale_member(&mut self, role: RoleId, account_id: AccountId) {
+        let Some(position) = self.role_member_positions.take((role, account_id)) else {
+            return;
+        };
+
+        if self.role_members.get((role, position)) == Some(account_id) {
+            self.role_members.remove((role, position));
+        }
+    }
+
+    /// remove_role_member swaps the member to remove with the last one
+    /// and pops it, so it doesn't need to shift the rest of the index
+    pub(crate) fn remove_role_member(&mut self, role: RoleId, account_id: AccountId) {
+        let Some(position) = self.role_member_positions.take((role, account_id)) else {
+            return;
+        };
+        let last = self.role_member_counts.get(role).unwrap_or(1) - 1;
+
+        if position != last {
+            if let Some(last_member) = self.role_members.get((role, last)) {
+                self.role_members.insert((role, position), &last_member);
+                self.role_member_positions.insert((role, last_member), &position);
+            }
+        }
+
+        self.role_members.remove((role, last));
+
+        if last == 0 {
+            self.role_member_counts.remove(role);
+        } else {
+            self.role_member_counts.insert(role, &last);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink::env::DefaultEnvironment;
+
+    #[ink::test]
+    fn role_members_are_indexed() {
+        let mut access_control = AccessControlData::<4>::new_enumerable();
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let role = 1;
+
+        access_control.set_role::<()>(accounts.alice, role);
+        access_control.set_role::<()>(accounts.bob, role);
+        access_control.set_role::<()>(accounts.charlie, role);
+
+        // granting a role twice doesn't index the account twice
+        access_control.set_role::<()>(accounts.bob, role);
+
+        assert_eq!(access_control.get_role_member_count(role), 3);
+        assert_eq!(access_control.get_role_member(role, 0), Some(accounts.alice));
+        assert_eq!(access_control.get_role_member(role, 1), Some(accounts.bob));
+        assert_eq!(access_control.get_role_member(role, 2), Some(accounts.charlie));
+        assert_eq!(access_control.get_role_member(role, 3), None);
+
+        // other roles aren't affected
+        assert_eq!(access_control.get_role_member_count(role + 1), 0);
+    }
+
+    #[ink::test]
+    fn revoked_members_are_removed_from_the_index() {
+        let mut access_control = AccessControlData::<4>::new_enumerable();
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let role = 1;
+
+        access_control.set_role::<()>(accounts.alice, role);
+        access_control.set_role::<()>(accounts.bob, role);
+        access_control.set_role::<()>(accounts.charlie, role);
+
+        // the last member takes the place of the revoked one
+        access_control.unset_role::<()>(accounts.alice, role);
+        assert_eq!(
+            access_control.role_members(role, 0, 10),
+            [accounts.charlie, accounts.bob]
+        );
+
+        // revoking from an account that doesn't hold the role does
+        // nothing
+        access_control.unset_role::<()>(accounts.alice, role);
+        assert_eq!(access_control.get_role_member_count(role), 2);
+
+        access_control.unset_role::<()>(accounts.bob, role);
+        access_control.unset_role::<()>(accounts.charlie, role);
+
+        assert_eq!(access_control.get_role_member_count(role), 0);
+        assert!(access_control.role_members(role, 0, 10).is_empty());
+        assert!(!access_control.role_member_counts.contains(role as RoleId));
+        assert!(!access_control
+            .role_member_positions
+            .contains((role as RoleId, accounts.charlie)));
+    }
+
+    #[ink::test]
+    fn role_members_paginates() {
+        let mut access_control = AccessControlData::<4>::new_enumerable();
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let role = 1;
+
+        for account in [accounts.alice, accounts.bob, accounts.charlie, accounts.django] {
+            access_control.set_role::<()>(account, role);
+        }
+
+        assert_eq!(access_control.role_members(role, 0, 2), [accounts.alice, accounts.bob]);
+        assert_eq!(
+            access_control.role_members(role, 2, 2),
+            [accounts.charlie, accounts.django]
+        );
+        assert_eq!(access_control.role_members(role, 3, 2), [accounts.django]);
+        assert!(access_control.role_members(role, 4, 2).is_empty());
+        assert!(access_control.role_members(role, 1, 0).is_empty());
+        assert_eq!(access_control.role_members(role, 3, u32::MAX), [accounts.django]);
+    }
+
+    #[ink::test]
+    fn members_are_not_indexed_unless_enumerable() {
+        let mut access_control = AccessControlData::<4>::new();
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let role = 1;
+
+        access_control.set_role::<()>(accounts.alice, role);
+
+        assert_eq!(access_control.get_role_member_count(role), 0);
+        assert!(!access_control.role_members.contains((role as RoleId, 0)));
+    }
+}
@@ -0,0 +1,52 @@
+use ink::primitives::AccountId;
+
+use crate::RoleId;
+
+/// AccessControlError enumerates the reasons an access control
+/// operation can be rejected for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum AccessControlError {
+    /// `account` needed to hold `role` to perform the operation.
+    MissingRole { account: AccountId, role: RoleId },
+    /// The role doesn't fit in the roles bitmap.
+    RoleOutOfRange,
+    /// An account tried to renounce a role on behalf of another
+    /// account.
+    BadConfirmation,
+    /// The role has a minimum delay that the grant doesn't respect,
+    /// see `set_grant_delay`.
+    DelayTooShort,
+    /// There's no scheduled grant of the role to the account that
+    /// hasn't matured yet.
+    GrantNotPending,
+    /// The scheduled grant hasn't matured yet.
+    GrantNotReady,
+    /// The role has to be offered with `offer_role` and accepted by
+    /// the account, see `set_role_acceptance`.
+    AcceptanceRequired,
+    /// The role wasn't offered to the account.
+    RoleNotOffered,
+    /// `account` needed to be the owner to perform the operation.
+    NotOwner { account: AccountId },
+    /// `account` tried to accept an ownership that isn't being
+    /// transferred to it.
+    NotPendingOwner { account: AccountId },
+    /// The default admin role can only change hands through a
+    /// transfer under the default admin rules, see
+    /// `enable_default_admin_rules`.
+    EnforcedDefaultAdminRules,
+    /// The operation can't be performed while the contract is paused.
+    EnforcedPause,
+    /// The operation can only be performed while the contract is
+    /// paused.
+    ExpectedPause,
+    /// `account` is frozen, see `freeze_account`.
+    AccountFrozen { account: AccountId },
+    /// The guardian role can't be suspended, since nobody could resume
+    /// it then.
+    GuardianSuspension,
+    /// Holding any of an empty set of roles was required, which no
+    /// account does.
+    EmptyRoles,
+}
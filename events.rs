@@ -0,0 +1,322 @@
+use ink::primitives::AccountId;
+
+use crate::{expiry::Timestamp, Expiry, RoleId};
+
+/// RoleGranted is emitted when `account` is granted `role`. `sender`
+/// is the account that originated the change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoleGranted {
+    pub role:    RoleId,
+    pub account: AccountId,
+    pub sender:  AccountId,
+}
+
+/// RoleRevoked is emitted when `role` is revoked from `account`,
+/// either by an admin or by `account` renouncing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoleRevoked {
+    pub role:    RoleId,
+    pub account: AccountId,
+    pub sender:  AccountId,
+}
+
+/// RoleAdminChanged is emitted when the admin of `role` changes from
+/// `previous_admin_role` to `new_admin_role`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoleAdminChanged {
+    pub role:                RoleId,
+    pub previous_admin_role: RoleId,
+    pub new_admin_role:      RoleId,
+}
+
+/// RoleGrantedUntil is emitted when `sender` grants `role` to `account`
+/// until `expiry`, after `RoleGranted` if the account didn't hold it
+/// yet. Nothing is emitted when the grant lapses, since no call is
+/// involved, so indexers have to tell from `expiry` when it does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoleGrantedUntil {
+    pub role:    RoleId,
+    pub account: AccountId,
+    pub sender:  AccountId,
+    pub expiry:  Expiry,
+}
+
+/// RoleGrantScheduled is emitted when `sender` schedules granting
+/// `role` to `account`, which takes effect at the `ready_at` block
+/// timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoleGrantScheduled {
+    pub role:     RoleId,
+    pub account:  AccountId,
+    pub sender:   AccountId,
+    pub ready_at: Timestamp,
+}
+
+/// RoleGrantExecuted is emitted when a scheduled grant of `role` to
+/// `account` is executed by `sender` after it matured. It's followed
+/// by the `RoleGranted` event of the grant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoleGrantExecuted {
+    pub role:    RoleId,
+    pub account: AccountId,
+    pub sender:  AccountId,
+}
+
+/// RoleGrantCancelled is emitted when a scheduled grant of `role` to
+/// `account` is cancelled by `sender` before it was executed, either
+/// through `cancel_grant` or by revoking the role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoleGrantCancelled {
+    pub role:    RoleId,
+    pub account: AccountId,
+    pub sender:  AccountId,
+}
+
+/// RoleOffered is emitted when `sender` offers `role` to `account`,
+/// which holds it once it accepts it. Its acceptance emits
+/// `RoleGranted`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoleOffered {
+    pub role:    RoleId,
+    pub account: AccountId,
+    pub sender:  AccountId,
+}
+
+/// RoleOfferWithdrawn is emitted when `sender` withdraws the offer of
+/// `role` to `account` before it was accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoleOfferWithdrawn {
+    pub role:    RoleId,
+    pub account: AccountId,
+    pub sender:  AccountId,
+}
+
+/// DefaultAdminTransferScheduled is emitted when the default admin
+/// starts transferring the role to `new_admin`, which can accept it
+/// from the `ready_at` block timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DefaultAdminTransferScheduled {
+    pub new_admin: AccountId,
+    pub ready_at:  Timestamp,
+}
+
+/// DefaultAdminTransferCancelled is emitted when the default admin
+/// cancels the transfer of the role to `new_admin`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DefaultAdminTransferCancelled {
+    pub new_admin: AccountId,
+}
+
+/// DefaultAdminDelayChangeScheduled is emitted when the default admin
+/// schedules replacing the delay of its transfers with `new_delay`,
+/// from the `effect_at` block timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DefaultAdminDelayChangeScheduled {
+    pub new_delay: Timestamp,
+    pub effect_at: Timestamp,
+}
+
+/// DefaultAdminDelayChangeCancelled is emitted when the default admin
+/// cancels replacing the delay of its transfers with `new_delay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DefaultAdminDelayChangeCancelled {
+    pub new_delay: Timestamp,
+}
+
+/// RoleSuspended is emitted when `sender` suspends `role`, which no
+/// account holds until it's resumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoleSuspended {
+    pub role:   RoleId,
+    pub sender: AccountId,
+}
+
+/// RoleResumed is emitted when `sender` resumes the suspended `role`,
+/// which its holders hold again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoleResumed {
+    pub role:   RoleId,
+    pub sender: AccountId,
+}
+
+/// RoleRevokedFromAll is emitted when `sender` revokes `role` from
+/// every account holding it, starting its `generation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoleRevokedFromAll {
+    pub role:       RoleId,
+    pub generation: u32,
+    pub sender:     AccountId,
+}
+
+/// AccountFrozen is emitted when `sender` freezes `account`, which
+/// holds no role until it's unfrozen or, if given, `until`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AccountFrozen {
+    pub account: AccountId,
+    pub sender:  AccountId,
+    pub until:   Option<Timestamp>,
+}
+
+/// AccountUnfrozen is emitted when `sender` unfreezes `account`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AccountUnfrozen {
+    pub account: AccountId,
+    pub sender:  AccountId,
+}
+
+/// Paused is emitted when `account` pauses the contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Paused {
+    pub account: AccountId,
+}
+
+/// Unpaused is emitted when `account` unpauses the contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Unpaused {
+    pub account: AccountId,
+}
+
+/// OwnershipTransferStarted is emitted when `previous_owner` starts
+/// transferring the ownership to `new_owner`, which still has to
+/// accept it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct OwnershipTransferStarted {
+    pub previous_owner: AccountId,
+    pub new_owner:      AccountId,
+}
+
+/// OwnershipTransferred is emitted when the owner changes from
+/// `previous_owner` to `new_owner`. Either of them is None if the
+/// contract had no owner, or the ownership was renounced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct OwnershipTransferred {
+    pub previous_owner: Option<AccountId>,
+    pub new_owner:      Option<AccountId>,
+}
+
+/// AccessControlEvents is implemented by the host contract to emit
+/// the events produced by `AccessControlData`.
+///
+/// ink! 4 requires events to be declared inside the contract module,
+/// so the library can't emit them by itself. Instead, every operation
+/// that changes roles takes the type implementing this trait and
+/// calls it after the change has been stored:
+///
+/// ```ignore
+/// use ink::codegen::{EmitEvent, StaticEnv};
+///
+/// impl AccessControlEvents for MyContract {
+///     fn emit_role_granted(event: access_control::RoleGranted) {
+///         Self::env().emit_event(RoleGranted {
+///             role:    event.role,
+///             account: event.account,
+///             sender:  event.sender,
+///         });
+///     }
+///     // ...
+/// }
+///
+/// self.access_control.set_role::<Self>(account, Self::ROLE);
+/// ```
+///
+/// The events of optional features, like scheduled grants or role
+/// suspensions, aren't emitted unless their methods are implemented too.
+/// The unit type implements it by not emitting anything.
+pub trait AccessControlEvents {
+    fn emit_role_granted(event: RoleGranted);
+
+    fn emit_role_revoked(event: RoleRevoked);
+
+    fn emit_role_admin_changed(event: RoleAdminChanged);
+
+    fn emit_role_granted_until(_: RoleGrantedUntil) {}
+
+    fn emit_role_grant_scheduled(_: RoleGrantScheduled) {}
+
+    fn emit_role_grant_executed(_: RoleGrantExecuted) {}
+
+    fn emit_role_grant_cancelled(_: RoleGrantCancelled) {}
+
+    fn emit_role_offered(_: RoleOffered) {}
+
+    fn emit_role_offer_withdrawn(_: RoleOfferWithdrawn) {}
+
+    fn emit_default_admin_transfer_scheduled(_: DefaultAdminTransferScheduled) {}
+
+    fn emit_default_admin_transfer_cancelled(_: DefaultAdminTransferCancelled) {}
+
+    fn emit_default_admin_delay_change_scheduled(_: DefaultAdminDelayChangeScheduled) {}
+
+    fn emit_default_admin_delay_change_cancelled(_: DefaultAdminDelayChangeCancelled) {}
+
+    fn emit_role_suspended(_: RoleSuspended) {}
+
+    fn emit_role_resumed(_: RoleResumed) {}
+
+    fn emit_account_frozen(_: AccountFrozen) {}
+
+    fn emit_account_unfrozen(_: AccountUnfrozen) {}
+
+    fn emit_role_revoked_from_all(_: RoleRevokedFromAll) {}
+}
+
+impl AccessControlEvents for () {
+    fn emit_role_granted(_: RoleGranted) {}
+
+    fn emit_role_revoked(_: RoleRevoked) {}
+
+    fn emit_role_admin_changed(_: RoleAdminChanged) {}
+}
+
+/// OwnableEvents is implemented by the host contract to emit the
+/// events produced by `OwnableData`, like `AccessControlEvents` does
+/// for `AccessControlData`. The unit type implements it by not
+/// emitting anything.
+pub trait OwnableEvents {
+    fn emit_ownership_transfer_started(event: OwnershipTransferStarted);
+
+    fn emit_ownership_transferred(event: OwnershipTransferred);
+}
+
+impl OwnableEvents for () {
+    fn emit_ownership_transfer_started(_: OwnershipTransferStarted) {}
+
+    fn emit_ownership_transferred(_: OwnershipTransferred) {}
+}
+
+/// PausableEvents is implemented by the host contract to emit the
+/// events produced by `PausableData`. The unit type implements it by
+/// not emitting anything.
+pub trait PausableEvents {
+    fn emit_paused(event: Paused);
+
+    fn emit_unpaused(event: Unpaused);
+}
+
+impl PausableEvents for () {
+    fn emit_paused(_: Paused) {}
+
+    fn emit_unpaused(_: Unpaused) {}
+}
@@ -0,0 +1,344 @@
+use ink::{
+    env::{DefaultEnvironment, Environment},
+    primitives::AccountId,
+};
+
+use crate::{
+    AccessControlData, AccessControlError, AccessControlEvents, Role, RoleGrantedUntil, RoleId,
+};
+
+pub(crate) type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+pub(crate) type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+
+/// Expiry is the moment a time-bound role stops being held, either a
+/// block number or a block timestamp in milliseconds. The role is held
+/// up to the block before it, or while the block timestamp is lower
+/// than it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum Expiry {
+    Block(BlockNumber),
+    Timestamp(Timestamp),
+}
+
+impl Expiry {
+    /// is_expired returns true if the current block is past the expiry
+    pub fn is_expired(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// remaining returns the number of blocks or milliseconds, matching
+    /// the kind of expiry, left until it expires
+    pub fn remaining(&self) -> u64 {
+        match *self {
+            Expiry::Block(block) => {
+                block.saturating_sub(ink::env::block_number::<DefaultEnvironment>()) as u64
+            }
+            Expiry::Timestamp(timestamp) => {
+                timestamp.saturating_sub(ink::env::block_timestamp::<DefaultEnvironment>())
+            }
+        }
+    }
+}
+
+impl<const N: usize, const PAGES: usize> AccessControlData<N, PAGES> {
+    /// set_role_until grants `role` to `account_id` without any
+    /// authorization until `expiry`, after which `has_role` and the
+    /// rest of queries treat it as not held. `RoleGrantedUntil` is
+    /// emitted through `E` along with `RoleGranted`.
+    ///
+    /// Granting the role again with `set_role` makes it permanent.
+    /// Expired roles stay in the account's bitmap, and in the members
+    /// index, until they're unset or cleared with `clear_expired_role`.
+    pub fn set_role_until<E: AccessControlEvents>(
+        &mut self,
+        account_id: AccountId,
+        role: impl Role,
+        expiry: Expiry,
+    ) {
+        let role = Self::index(role);
+        self.assert_default_admin_rules(role);
+
+        self.set_role_by::<E>(Self::caller(), account_id, role);
+        self.set_expiry::<E>(Self::caller(), account_id, role, expiry);
+    }
+
+    /// grant_role_until is the authorized version of set_role_until,
+    /// `caller` must hold the admin role of `role`
+    pub fn grant_role_until<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        account_id: AccountId,
+        role: impl Role,
+        expiry: Expiry,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+        Self::check_role(role)?;
+        self.check_role_admin(caller, role)?;
+        self.check_direct_grant(role)?;
+
+        self.set_role_by::<E>(caller, account_id, role);
+        self.set_expiry::<E>(caller, account_id, role, expiry);
+        Ok(())
+    }
+
+    /// role_expiry returns when the grant of `role` to `account_id`
+    /// expires, or None if it's permanent or not held at all
+    pub fn role_expiry(&self, account_id: AccountId, role: impl Role) -> Option<Expiry> {
+        if self.timed_grants == 0 {
+            return None;
+        }
+
+        self.role_expiries
+            .get((account_id, Self::index(role) as RoleId))
+    }
+
+    /// remaining_validity returns the number of blocks or milliseconds,
+    /// matching the kind of expiry, left until the grant of `role` to
+    /// `account_id` expires. It's None if the grant is permanent or
+    /// there's no grant, and 0 if it already expired.
+    pub fn remaining_validity(&self, account_id: AccountId, role: impl Role) -> Option<u64> {
+        self.role_expiry(account_id, role)
+            .map(|expiry| expiry.remaining())
+    }
+
+    /// clear_expired_role unsets `role` from `account_id` if its grant
+    /// expired, refunding its storage and removing it from the members
+    /// index. Anyone can call it, since the role is no longer held.
+    /// Returns true if the role was cleared.
+    pub fn clear_expired_role<E: AccessControlEvents>(
+        &mut self,
+        account_id: AccountId,
+        role: impl Role,
+    ) -> bool {
+        let role = Self::index(role);
+
+        if !self.is_expired(account_id, role) {
+            return false;
+        }
+
+        self.unset_role_by::<E>(Self::caller(), account_id, role);
+        true
+    }
+
+    /// is_expired returns true if `account_id` was granted `role` until
+    /// an expiry that already passed
+    pub(crate) fn is_expired(&self, account_id: AccountId, role: usize) -> bool {
+        self.timed_grants > 0
+            && self
+                .role_expiries
+                .get((account_id, role as RoleId))
+                .is_some_and(|expiry| expiry.is_expired())
+    }
+
+    /// clear_expiry makes the grant of `role` to `account_id`
+    /// permanent, if it was time-bound
+    pub(crate) fn clear_expiry(&mut self, account_id: AccountId, role: usize) {
+        if self.timed_grants == 0 {
+            return;
+        }
+
+        if self.role_expiries.take((account_id, role as RoleId)).is_some() {
+            self.timed_grants -= 1;
+        }
+    }
+
+    fn set_expiry<E: AccessControlEvents>(
+        &mut self,
+        sender: AccountId,
+        account_id: AccountId,
+        role: usize,
+        expiry: Expiry,
+    ) {
+        if self
+            .role_expiries
+            .insert((account_id, role as RoleId), &expiry)
+            .is_none()
+        {
+            self.timed_grants += 1;
+        }
+
+        E::emit_role_granted_until(RoleGrantedUntil {
+            role: role as RoleId,
+            account: account_id,
+            sender,
+            expiry,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RoleAdminChanged, RoleGranted, RoleMask, RoleRevoked, DEFAULT_ADMIN_ROLE};
+    use std::cell::RefCell;
+
+    std::thread_local! {
+        static GRANTED_UNTIL: RefCell<Vec<RoleGrantedUntil>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Recorder keeps the `RoleGrantedUntil` events emitted
+    struct Recorder;
+
+    impl AccessControlEvents for Recorder {
+        fn emit_role_granted(_: RoleGranted) {}
+
+        fn emit_role_revoked(_: RoleRevoked) {}
+
+        fn emit_role_admin_changed(_: RoleAdminChanged) {}
+
+        fn emit_role_granted_until(event: RoleGrantedUntil) {
+            GRANTED_UNTIL.with(|emitted| emitted.borrow_mut().push(event));
+        }
+    }
+
+    fn advance_blocks(blocks: u32) {
+        for _ in 0..blocks {
+            ink::env::test::advance_block::<DefaultEnvironment>();
+        }
+    }
+
+    #[ink::test]
+    fn roles_expire_at_block() {
+        let mut access_control = AccessControlData::<4>::new();
+        let account = AccountId::from([1u8; 32]);
+        let (role, other) = (1, 2);
+        let now = ink::env::block_number::<DefaultEnvironment>();
+
+        access_control.set_role_until::<()>(account, role, Expiry::Block(now + 2));
+        access_control.set_role::<()>(account, other);
+
+        assert!(access_control.has_role(account, role));
+        assert_eq!(access_control.role_expiry(account, role), Some(Expiry::Block(now + 2)));
+        assert_eq!(access_control.remaining_validity(account, role), Some(2));
+        assert_eq!(access_control.remaining_validity(account, other), None);
+
+        advance_blocks(1);
+        assert!(access_control.has_role(account, role));
+        assert_eq!(access_control.remaining_validity(account, role), Some(1));
+
+        advance_blocks(1);
+        assert!(!access_control.has_role(account, role));
+        assert_eq!(access_control.remaining_validity(account, role), Some(0));
+        assert_eq!(
+            access_control.ensure_role(account, role),
+            Err(AccessControlError::MissingRole {
+                account,
+                role: role as RoleId,
+            })
+        );
+
+        // the expired role doesn't count for the rest of queries either
+        assert!(!access_control.has_any_role(account, &[role]));
+        assert!(!access_control.has_all_roles(account, &[role, other]));
+        assert!(access_control.has_any_of(account, &RoleMask::of(&[role, other])));
+        assert_eq!(access_control.roles_of(account), [other]);
+    }
+
+    #[ink::test]
+    fn roles_expire_at_timestamp() {
+        let mut access_control = AccessControlData::<4>::new();
+        let account = AccountId::from([1u8; 32]);
+        let role = 1;
+
+        ink::env::test::set_block_timestamp::<DefaultEnvironment>(1_000);
+        access_control.set_role_until::<()>(account, role, Expiry::Timestamp(1_500));
+
+        assert!(access_control.has_role(account, role));
+        assert_eq!(access_control.remaining_validity(account, role), Some(500));
+
+        ink::env::test::set_block_timestamp::<DefaultEnvironment>(1_500);
+        assert!(!access_control.has_role(account, role));
+    }
+
+    #[ink::test]
+    fn time_bound_grants_emit_their_expiry() {
+        let mut access_control = AccessControlData::<4>::new();
+        let (admin, account) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+
+        access_control.set_role::<()>(admin, DEFAULT_ADMIN_ROLE);
+        access_control.set_role_until::<Recorder>(account, 1, Expiry::Block(10));
+        access_control
+            .grant_role_until::<Recorder>(admin, account, 2, Expiry::Timestamp(1_500))
+            .unwrap();
+
+        assert_eq!(
+            GRANTED_UNTIL.with(|emitted| emitted.take()),
+            [
+                RoleGrantedUntil {
+                    role: 1,
+                    account,
+                    sender: ink::env::caller::<DefaultEnvironment>(),
+                    expiry: Expiry::Block(10),
+                },
+                RoleGrantedUntil {
+                    role: 2,
+                    account,
+                    sender: admin,
+                    expiry: Expiry::Timestamp(1_500),
+                },
+            ]
+        );
+    }
+
+    #[ink::test]
+    fn granting_again_makes_roles_permanent() {
+        let mut access_control = AccessControlData::<4>::new();
+        let account = AccountId::from([1u8; 32]);
+        let role = 1;
+        let now = ink::env::block_number::<DefaultEnvironment>();
+
+        access_control.set_role_until::<()>(account, role, Expiry::Block(now + 1));
+        access_control.set_role::<()>(account, role);
+        assert_eq!(access_control.timed_grants, 0);
+
+        advance_blocks(1);
+        assert!(access_control.has_role(account, role));
+
+        // revoking forgets the expiry too
+        access_control.set_role_until::<()>(account, role, Expiry::Block(now + 2));
+        access_control.unset_role::<()>(account, role);
+        assert_eq!(access_control.role_expiry(account, role), None);
+        assert_eq!(access_control.timed_grants, 0);
+    }
+
+    #[ink::test]
+    fn grant_role_until_checks_admin() {
+        let mut access_control = AccessControlData::<4>::new();
+        let (admin, account) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+        let role = 1;
+        let expiry = Expiry::Block(ink::env::block_number::<DefaultEnvironment>() + 1);
+
+        assert_eq!(
+            access_control.grant_role_until::<()>(admin, account, role, expiry),
+            Err(AccessControlError::MissingRole {
+                account: admin,
+                role:    DEFAULT_ADMIN_ROLE as RoleId,
+            })
+        );
+
+        access_control.set_role::<()>(admin, DEFAULT_ADMIN_ROLE);
+        assert_eq!(access_control.grant_role_until::<()>(admin, account, role, expiry), Ok(()));
+        assert!(access_control.has_role(account, role));
+    }
+
+    #[ink::test]
+    fn clear_expired_role_works() {
+        let mut access_control = AccessControlData::<4>::new_enumerable();
+        let account = AccountId::from([1u8; 32]);
+        let role = 1;
+        let now = ink::env::block_number::<DefaultEnvironment>();
+
+        access_control.set_role_until::<()>(account, role, Expiry::Block(now + 1));
+        assert!(!access_control.clear_expired_role::<()>(account, role));
+
+        advance_blocks(1);
+        assert!(access_control.clear_expired_role::<()>(account, role));
+        assert_eq!(access_control.get_role_member_count(role), 0);
+        assert!(!access_control.roles_per_account.contains((account, 0)));
+        assert_eq!(access_control.timed_grants, 0);
+    }
+}
@@ -0,0 +1,237 @@
+use ink::{env::DefaultEnvironment, primitives::AccountId};
+
+use crate::{
+    expiry::Timestamp, AccessControlData, AccessControlError, AccessControlEvents, AccountFrozen,
+    AccountUnfrozen, Role, RoleId,
+};
+
+impl<const N: usize, const PAGES: usize> AccessControlData<N, PAGES> {
+    /// set_guardian_role sets the role that can freeze and unfreeze
+    /// accounts, and suspend and resume roles, `DEFAULT_ADMIN_ROLE` by
+    /// default.
+    ///
+    /// Like `set_role_admin` it doesn't perform any authorization,
+    /// it's meant to be used while setting up the contract or behind
+    /// the contract's own checks.
+    pub fn set_guardian_role(&mut self, role: impl Role) {
+        self.guardian_role = Self::index(role) as RoleId;
+    }
+
+    /// get_guardian_role returns the role that can freeze and unfreeze
+    /// accounts, and suspend and resume roles
+    pub fn get_guardian_role(&self) -> usize {
+        self.guardian_role as usize
+    }
+
+    /// freeze_account freezes `account_id` if `caller` holds the
+    /// guardian role, so that `has_role` and the rest of queries treat
+    /// it as not holding any role, until it's unfrozen or, if given,
+    /// the `until` block timestamp. Its roles stay in its bitmaps, so
+    /// unfreezing it restores them.
+    ///
+    /// Freezing an account that's already frozen replaces its
+    /// automatic unfreeze time. A frozen guardian can't unfreeze
+    /// itself.
+    pub fn freeze_account<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        account_id: AccountId,
+        until: Option<Timestamp>,
+    ) -> Result<(), AccessControlError> {
+        self.ensure_role(caller, self.get_guardian_role())?;
+
+        if self.frozen_accounts.insert(account_id, &until).is_none() {
+            self.frozen_count += 1;
+        }
+
+        E::emit_account_frozen(AccountFrozen {
+            account: account_id,
+            sender:  caller,
+            until,
+        });
+
+        Ok(())
+    }
+
+    /// unfreeze_account unfreezes `account_id` if `caller` holds the
+    /// guardian role, emitting `AccountUnfrozen` through `E` if it was
+    /// frozen. Freezes that were lifted automatically are cleared as
+    /// well, refunding their storage.
+    pub fn unfreeze_account<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        account_id: AccountId,
+    ) -> Result<(), AccessControlError> {
+        self.ensure_role(caller, self.get_guardian_role())?;
+
+        if self.frozen_count == 0 || self.frozen_accounts.take(account_id).is_none() {
+            return Ok(());
+        }
+        self.frozen_count -= 1;
+
+        E::emit_account_unfrozen(AccountUnfrozen {
+            account: account_id,
+            sender:  caller,
+        });
+
+        Ok(())
+    }
+
+    /// is_frozen returns true if `account_id` is frozen
+    pub fn is_frozen(&self, account_id: AccountId) -> bool {
+        if self.frozen_count == 0 {
+            return false;
+        }
+
+        match self.frozen_accounts.get(account_id) {
+            Some(Some(until)) => until > ink::env::block_timestamp::<DefaultEnvironment>(),
+            Some(None) => true,
+            None => false,
+        }
+    }
+
+    /// unfreeze_time returns the block timestamp at which the freeze of
+    /// `account_id` is lifted automatically, or None if it's not frozen
+    /// or it's frozen until it's unfrozen
+    pub fn unfreeze_time(&self, account_id: AccountId) -> Option<Timestamp> {
+        if !self.is_frozen(account_id) {
+            return None;
+        }
+
+        self.frozen_accounts.get(account_id).flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RoleMask, DEFAULT_ADMIN_ROLE};
+    use ink::env::test::set_block_timestamp;
+
+    const GUARDIAN: usize = 2;
+
+    fn setup() -> (AccessControlData<4>, AccountId, AccountId) {
+        let mut access_control = AccessControlData::<4>::new();
+        let (guardian, operator) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+
+        access_control.set_guardian_role(GUARDIAN);
+        access_control.set_role::<()>(guardian, GUARDIAN);
+        access_control.set_account_roles::<()>(operator, &[DEFAULT_ADMIN_ROLE, 1]);
+        set_block_timestamp::<DefaultEnvironment>(1_000);
+
+        (access_control, guardian, operator)
+    }
+
+    #[ink::test]
+    fn frozen_accounts_dont_hold_roles() {
+        let (mut access_control, guardian, operator) = setup();
+
+        assert_eq!(access_control.freeze_account::<()>(guardian, operator, None), Ok(()));
+        assert!(access_control.is_frozen(operator));
+        assert_eq!(access_control.unfreeze_time(operator), None);
+
+        assert!(!access_control.has_role(operator, 1));
+        assert!(!access_control.has_any_role(operator, &[DEFAULT_ADMIN_ROLE, 1]));
+        assert!(!access_control.has_all_of(operator, &RoleMask::of(&[1])));
+        assert!(access_control.roles_of(operator).is_empty());
+        assert!(access_control.role_mask_of(operator).is_empty());
+
+        // a frozen admin can't administer
+        assert_eq!(
+            access_control.grant_role::<()>(operator, guardian, 3),
+            Err(AccessControlError::MissingRole {
+                account: operator,
+                role:    DEFAULT_ADMIN_ROLE as RoleId,
+            })
+        );
+
+        assert_eq!(access_control.unfreeze_account::<()>(guardian, operator), Ok(()));
+        assert!(!access_control.is_frozen(operator));
+        assert_eq!(access_control.roles_of(operator), [DEFAULT_ADMIN_ROLE, 1]);
+        assert_eq!(access_control.frozen_count, 0);
+    }
+
+    #[ink::test]
+    fn only_guardians_can_freeze() {
+        let (mut access_control, guardian, operator) = setup();
+        let missing_role = Err(AccessControlError::MissingRole {
+            account: operator,
+            role:    GUARDIAN as RoleId,
+        });
+
+        assert_eq!(access_control.freeze_account::<()>(operator, guardian, None), missing_role);
+
+        access_control.freeze_account::<()>(guardian, operator, None).unwrap();
+        assert_eq!(access_control.unfreeze_account::<()>(operator, operator), missing_role);
+        assert!(access_control.is_frozen(operator));
+    }
+
+    #[ink::test]
+    fn freezes_can_be_lifted_automatically() {
+        let (mut access_control, guardian, operator) = setup();
+
+        access_control.freeze_account::<()>(guardian, operator, Some(1_500)).unwrap();
+        assert_eq!(access_control.unfreeze_time(operator), Some(1_500));
+        assert!(!access_control.has_role(operator, 1));
+
+        set_block_timestamp::<DefaultEnvironment>(1_500);
+        assert!(!access_control.is_frozen(operator));
+        assert_eq!(access_control.unfreeze_time(operator), None);
+        assert!(access_control.has_role(operator, 1));
+
+        // the lifted freeze can still be cleared
+        access_control.unfreeze_account::<()>(guardian, operator).unwrap();
+        assert_eq!(access_control.frozen_count, 0);
+    }
+
+    #[ink::test]
+    fn frozen_default_admins_cant_transfer_the_role() {
+        let mut access_control = AccessControlData::<4>::new();
+        let (guardian, admin) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+        let attacker = AccountId::from([3u8; 32]);
+
+        set_block_timestamp::<DefaultEnvironment>(1_000);
+        access_control.enable_default_admin_rules::<()>(admin, 500);
+        access_control.set_guardian_role(GUARDIAN);
+        access_control.set_role::<()>(guardian, GUARDIAN);
+        access_control.freeze_account::<()>(guardian, admin, None).unwrap();
+
+        let missing_role = Err(AccessControlError::MissingRole {
+            account: admin,
+            role:    DEFAULT_ADMIN_ROLE as RoleId,
+        });
+        assert_eq!(
+            access_control.begin_default_admin_transfer::<()>(admin, attacker),
+            missing_role
+        );
+        assert_eq!(access_control.change_default_admin_delay::<()>(admin, 0), missing_role);
+        assert_eq!(access_control.cancel_default_admin_transfer::<()>(admin), missing_role);
+        assert_eq!(access_control.pending_default_admin(), None);
+    }
+
+    #[ink::test]
+    fn frozen_accounts_cant_accept_the_default_admin_role() {
+        let mut access_control = AccessControlData::<4>::new();
+        let (guardian, admin) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+        let new_admin = AccountId::from([3u8; 32]);
+
+        set_block_timestamp::<DefaultEnvironment>(1_000);
+        access_control.enable_default_admin_rules::<()>(admin, 500);
+        access_control.set_guardian_role(GUARDIAN);
+        access_control.set_role::<()>(guardian, GUARDIAN);
+
+        access_control.begin_default_admin_transfer::<()>(admin, new_admin).unwrap();
+        access_control.freeze_account::<()>(guardian, new_admin, None).unwrap();
+
+        set_block_timestamp::<DefaultEnvironment>(1_500);
+        assert_eq!(
+            access_control.accept_default_admin_transfer::<()>(new_admin),
+            Err(AccessControlError::AccountFrozen { account: new_admin })
+        );
+        assert_eq!(access_control.default_admin(), Some(admin));
+
+        access_control.unfreeze_account::<()>(guardian, new_admin).unwrap();
+        assert_eq!(access_control.accept_default_admin_transfer::<()>(new_admin), Ok(()));
+        assert_eq!(access_control.default_admin(), Some(new_admin));
+    }
+}
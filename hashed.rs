@@ -0,0 +1,510 @@
+use ink::{env::DefaultEnvironment, primitives::AccountId, storage::Mapping};
+
+use crate::{
+    AccessControlError, AccessControlEvents, RoleAdminChanged, RoleGranted, RoleId, RoleRevoked,
+};
+
+/// DEFAULT_ADMIN_ROLE_ID is the admin of every role of
+/// `HashedAccessControlData` that hasn't been assigned a different
+/// one, following the openzeppelin convention of using 0 for it.
+pub const DEFAULT_ADMIN_ROLE_ID: RoleId = 0;
+
+/// role_hash returns the blake2b-256 hash of `name`. It's a const fn,
+/// so the hash is computed at compile time when used in a constant.
+pub const fn role_hash(name: &str) -> [u8; 32] {
+    blake2b_256(name.as_bytes())
+}
+
+/// role_id derives a role id from its name, taking the first 4 bytes
+/// of `role_hash(name)` as a big endian u32. It's the same convention
+/// as `ink::selector_id!`, which OpenBrush uses for its role ids:
+///
+/// ```ignore
+/// const MINTER: RoleId = role_id("MINTER");
+/// ```
+///
+/// The hash is truncated so that the id fits in a `RoleId`, which
+/// makes these ids not compatible with openzeppelin's 32-byte ones.
+/// Two names share an id with a probability of about 1 in 2^32 per
+/// pair, and accounts holding one of two colliding roles would hold
+/// the other too, so the `access_control` attribute fails to compile
+/// if two of the `role_id` constants of a contract collide:
+///
+/// ```compile_fail
+/// #[access_control::access_control]
+/// #[ink::contract]
+/// mod contract {
+///     use access_control::{role_id, HashedAccessControlData, RoleId};
+///
+///     #[ink(storage)]
+///     pub struct Contract {
+///         access_control: HashedAccessControlData,
+///     }
+///
+///     impl Contract {
+///         // both names hash to 0xd65cbe1d
+///         pub const MINTER: RoleId = role_id("ROLE_97688");
+///         pub const BURNER: RoleId = role_id("ROLE_105546");
+///
+///         #[ink(constructor)]
+///         pub fn new() -> Self {
+///             Self { access_control: HashedAccessControlData::new() }
+///         }
+///
+///         #[ink(message)]
+///         pub fn get(&self) {}
+///     }
+/// }
+/// ```
+pub const fn role_id(name: &str) -> RoleId {
+    let hash = role_hash(name);
+    RoleId::from_be_bytes([hash[0], hash[1], hash[2], hash[3]])
+}
+
+/// HashedAccessControlData is an alternative to `AccessControlData`
+/// where roles are identified by a `RoleId` derived from their name
+/// with `role_id`, instead of by a bit position.
+///
+/// There's no limit to the number of roles and the ids are stable and
+/// self-describing, at the cost of one storage entry for every role
+/// held by an account instead of one bitmap per account.
+#[derive(Debug)]
+#[ink::storage_item]
+pub struct HashedAccessControlData {
+    /// The set of (role, account) pairs where the account holds the
+    /// role.
+    pub members: Mapping<(RoleId, AccountId), ()>,
+
+    /// An association between a role and the role that administers
+    /// it. Roles without an entry are administered by
+    /// `DEFAULT_ADMIN_ROLE_ID`.
+    pub admin_roles: Mapping<RoleId, RoleId>,
+}
+
+impl Default for HashedAccessControlData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HashedAccessControlData {
+    pub fn new() -> Self {
+        HashedAccessControlData {
+            members:     Mapping::new(),
+            admin_roles: Mapping::new(),
+        }
+    }
+
+    /// set_role grants `role` to `account_id` without any
+    /// authorization, emitting `RoleGranted` through `E` if the account
+    /// didn't hold it yet
+    pub fn set_role<E: AccessControlEvents>(&mut self, account_id: AccountId, role: RoleId) {
+        self.set_role_by::<E>(Self::caller(), account_id, role);
+    }
+
+    /// unset_role revokes `role` from `account_id` without any
+    /// authorization, emitting `RoleRevoked` through `E` if the account
+    /// held it
+    pub fn unset_role<E: AccessControlEvents>(&mut self, account_id: AccountId, role: RoleId) {
+        self.unset_role_by::<E>(Self::caller(), account_id, role);
+    }
+
+    /// has_role returns true if `account_id` holds `role`
+    pub fn has_role(&self, account_id: AccountId, role: RoleId) -> bool {
+        self.members.contains((role, account_id))
+    }
+
+    /// ensure_role fails with `MissingRole` if `account_id` doesn't
+    /// hold `role`
+    pub fn ensure_role(&self, account_id: AccountId, role: RoleId) -> Result<(), AccessControlError> {
+        if !self.has_role(account_id, role) {
+            return Err(AccessControlError::MissingRole {
+                account: account_id,
+                role,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// has_any_role returns true if `account_id` holds any of `roles`.
+    /// Every role is its own storage entry, so each one checked is a
+    /// read.
+    pub fn has_any_role(&self, account_id: AccountId, roles: &[RoleId]) -> bool {
+        roles.iter().any(|role| self.has_role(account_id, *role))
+    }
+
+    /// has_all_roles returns true if `account_id` holds all of `roles`
+    pub fn has_all_roles(&self, account_id: AccountId, roles: &[RoleId]) -> bool {
+        roles.iter().all(|role| self.has_role(account_id, *role))
+    }
+
+    /// ensure_any_role fails with `MissingRole` for the first of
+    /// `roles` if `account_id` doesn't hold any of them, and with
+    /// `EmptyRoles` if there are none
+    pub fn ensure_any_role(
+        &self,
+        account_id: AccountId,
+        roles: &[RoleId],
+    ) -> Result<(), AccessControlError> {
+        match roles.first() {
+            Some(role) if !self.has_any_role(account_id, roles) => {
+                self.ensure_role(account_id, *role)
+            }
+            Some(_) => Ok(()),
+            None => Err(AccessControlError::EmptyRoles),
+        }
+    }
+
+    /// ensure_all_roles fails with `MissingRole` for the first of
+    /// `roles` that `account_id` doesn't hold
+    pub fn ensure_all_roles(
+        &self,
+        account_id: AccountId,
+        roles: &[RoleId],
+    ) -> Result<(), AccessControlError> {
+        roles
+            .iter()
+            .try_for_each(|role| self.ensure_role(account_id, *role))
+    }
+
+    /// get_role_admin returns the role that administers `role`
+    pub fn get_role_admin(&self, role: RoleId) -> RoleId {
+        self.admin_roles.get(role).unwrap_or(DEFAULT_ADMIN_ROLE_ID)
+    }
+
+    /// set_role_admin makes `admin_role` the admin of `role` and emits
+    /// `RoleAdminChanged` through `E`. It doesn't perform any
+    /// authorization.
+    pub fn set_role_admin<E: AccessControlEvents>(&mut self, role: RoleId, admin_role: RoleId) {
+        let previous_admin_role = self.get_role_admin(role);
+
+        if admin_role == DEFAULT_ADMIN_ROLE_ID {
+            self.admin_roles.remove(role);
+        } else {
+            self.admin_roles.insert(role, &admin_role);
+        }
+
+        E::emit_role_admin_changed(RoleAdminChanged {
+            role,
+            previous_admin_role,
+            new_admin_role: admin_role,
+        });
+    }
+
+    /// grant_role sets `role` to `account_id` if `caller` holds the
+    /// admin role of `role`
+    pub fn grant_role<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        account_id: AccountId,
+        role: RoleId,
+    ) -> Result<(), AccessControlError> {
+        self.ensure_role(caller, self.get_role_admin(role))?;
+        self.set_role_by::<E>(caller, account_id, role);
+        Ok(())
+    }
+
+    /// revoke_role unsets `role` from `account_id` if `caller` holds
+    /// the admin role of `role`
+    pub fn revoke_role<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        account_id: AccountId,
+        role: RoleId,
+    ) -> Result<(), AccessControlError> {
+        self.ensure_role(caller, self.get_role_admin(role))?;
+        self.unset_role_by::<E>(caller, account_id, role);
+        Ok(())
+    }
+
+    /// renounce_role unsets `role` from `caller`. `account_id` must be
+    /// equal to `caller`.
+    pub fn renounce_role<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        account_id: AccountId,
+        role: RoleId,
+    ) -> Result<(), AccessControlError> {
+        if caller != account_id {
+            return Err(AccessControlError::BadConfirmation);
+        }
+
+        self.unset_role_by::<E>(caller, account_id, role);
+        Ok(())
+    }
+
+    fn set_role_by<E: AccessControlEvents>(
+        &mut self,
+        sender: AccountId,
+        account_id: AccountId,
+        role: RoleId,
+    ) {
+        if self.has_role(account_id, role) {
+            return;
+        }
+
+        self.members.insert((role, account_id), &());
+        E::emit_role_granted(RoleGranted {
+            role,
+            account: account_id,
+            sender,
+        });
+    }
+
+    fn unset_role_by<E: AccessControlEvents>(
+        &mut self,
+        sender: AccountId,
+        account_id: AccountId,
+        role: RoleId,
+    ) {
+        if !self.has_role(account_id, role) {
+            return;
+        }
+
+        self.members.remove((role, account_id));
+        E::emit_role_revoked(RoleRevoked {
+            role,
+            account: account_id,
+            sender,
+        });
+    }
+
+    fn caller() -> AccountId {
+        ink::env::caller::<DefaultEnvironment>()
+    }
+}
+
+const BLAKE2B_IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const BLAKE2B_SIGMA: [[usize; 16]; 12] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+];
+
+/// blake2b_256 is a const implementation of BLAKE2b (RFC 7693) with a
+/// 32 bytes digest and no key, the hash used by ink! selectors
+const fn blake2b_256(input: &[u8]) -> [u8; 32] {
+    let mut h = BLAKE2B_IV;
+    // parameter block: 32 bytes digest, no key, fanout and depth of 1
+    h[0] ^= 0x01010020;
+
+    let mut offset = 0;
+    // the last block is always compressed with the final flag, even if
+    // it's full
+    while input.len() - offset > 128 {
+        offset += 128;
+        blake2b_compress(&mut h, input, offset - 128, offset as u128, false);
+    }
+    blake2b_compress(&mut h, input, offset, input.len() as u128, true);
+
+    let mut out = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        out[i] = (h[i / 8] >> (8 * (i % 8))) as u8;
+        i += 1;
+    }
+    out
+}
+
+/// blake2b_compress mixes the block of `input` starting at `start`
+/// into `h`. Bytes past the end of `input` are read as zeroes.
+const fn blake2b_compress(h: &mut [u64; 8], input: &[u8], start: usize, t: u128, last: bool) {
+    let mut m = [0u64; 16];
+    let mut i = 0;
+    while i < 128 && start + i < input.len() {
+        m[i / 8] |= (input[start + i] as u64) << (8 * (i % 8));
+        i += 1;
+    }
+
+    let mut v = [0u64; 16];
+    let mut i = 0;
+    while i < 8 {
+        v[i] = h[i];
+        v[i + 8] = BLAKE2B_IV[i];
+        i += 1;
+    }
+    v[12] ^= t as u64;
+    v[13] ^= (t >> 64) as u64;
+    if last {
+        v[14] = !v[14];
+    }
+
+    let mut round = 0;
+    while round < 12 {
+        let s = &BLAKE2B_SIGMA[round];
+        blake2b_mix(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+        blake2b_mix(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+        blake2b_mix(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        blake2b_mix(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+        blake2b_mix(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+        blake2b_mix(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        blake2b_mix(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+        blake2b_mix(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+        round += 1;
+    }
+
+    let mut i = 0;
+    while i < 8 {
+        h[i] ^= v[i] ^ v[i + 8];
+        i += 1;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+const fn blake2b_mix(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink::env::hash::{Blake2x256, CryptoHash};
+
+    const MINTER: RoleId = role_id("MINTER");
+
+    fn blake2x256(input: &[u8]) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        Blake2x256::hash(input, &mut out);
+        out
+    }
+
+    #[test]
+    fn role_hash_matches_blake2x256() {
+        assert_eq!(
+            role_hash(""),
+            [
+                0x0e, 0x57, 0x51, 0xc0, 0x26, 0xe5, 0x43, 0xb2, 0xe8, 0xab, 0x2e, 0xb0, 0x60,
+                0x99, 0xda, 0xa1, 0xd1, 0xe5, 0xdf, 0x47, 0x77, 0x8f, 0x77, 0x87, 0xfa, 0xab,
+                0x45, 0xcd, 0xf1, 0x2f, 0xe3, 0xa8,
+            ]
+        );
+
+        // cover names that span several blocks, including one that
+        // fills the last block exactly
+        let long = "R".repeat(300);
+        for name in ["MINTER", &long[..127], &long[..128], &long[..129], &long[..256], &long] {
+            assert_eq!(role_hash(name), blake2x256(name.as_bytes()), "{}", name.len());
+        }
+    }
+
+    #[test]
+    fn role_id_matches_selector_id() {
+        assert_eq!(MINTER, ink::selector_id!("MINTER"));
+        assert_eq!(role_id("PAUSER"), ink::selector_id!("PAUSER"));
+
+        // the collision the example of role_id fails to compile with
+        assert_eq!(role_id("ROLE_97688"), 0xd65cbe1d);
+        assert_eq!(role_id("ROLE_105546"), 0xd65cbe1d);
+    }
+
+    #[ink::test]
+    fn set_and_unset_role_work() {
+        let mut access_control = HashedAccessControlData::new();
+        let account = AccountId::from([1u8; 32]);
+
+        access_control.set_role::<()>(account, MINTER);
+        assert!(access_control.has_role(account, MINTER));
+        assert!(!access_control.has_role(account, role_id("BURNER")));
+
+        access_control.unset_role::<()>(account, MINTER);
+        assert!(!access_control.has_role(account, MINTER));
+        assert!(!access_control.members.contains((MINTER, account)));
+    }
+
+    #[ink::test]
+    fn grant_and_revoke_role_work() {
+        let mut access_control = HashedAccessControlData::new();
+        let admin = AccountId::from([1u8; 32]);
+        let account = AccountId::from([2u8; 32]);
+
+        access_control.set_role::<()>(admin, DEFAULT_ADMIN_ROLE_ID);
+
+        assert_eq!(access_control.grant_role::<()>(admin, account, MINTER), Ok(()));
+        assert!(access_control.has_role(account, MINTER));
+
+        assert_eq!(
+            access_control.grant_role::<()>(account, admin, MINTER),
+            Err(AccessControlError::MissingRole {
+                account,
+                role: DEFAULT_ADMIN_ROLE_ID,
+            })
+        );
+
+        assert_eq!(access_control.revoke_role::<()>(admin, account, MINTER), Ok(()));
+        assert!(!access_control.has_role(account, MINTER));
+    }
+
+    #[ink::test]
+    fn role_admin_works() {
+        let mut access_control = HashedAccessControlData::new();
+        let manager = AccountId::from([1u8; 32]);
+        let account = AccountId::from([2u8; 32]);
+        let manager_role = role_id("MINTER_MANAGER");
+
+        assert_eq!(access_control.get_role_admin(MINTER), DEFAULT_ADMIN_ROLE_ID);
+
+        access_control.set_role_admin::<()>(MINTER, manager_role);
+        access_control.set_role::<()>(manager, manager_role);
+
+        assert_eq!(access_control.get_role_admin(MINTER), manager_role);
+        assert_eq!(access_control.grant_role::<()>(manager, account, MINTER), Ok(()));
+
+        assert_eq!(
+            access_control.renounce_role::<()>(manager, account, MINTER),
+            Err(AccessControlError::BadConfirmation)
+        );
+        assert_eq!(access_control.renounce_role::<()>(account, account, MINTER), Ok(()));
+        assert!(!access_control.has_role(account, MINTER));
+    }
+
+    #[ink::test]
+    fn any_and_all_roles_work() {
+        let mut access_control = HashedAccessControlData::new();
+        let account = AccountId::from([1u8; 32]);
+        let burner = role_id("BURNER");
+
+        access_control.set_role::<()>(account, MINTER);
+
+        assert!(access_control.has_any_role(account, &[burner, MINTER]));
+        assert!(!access_control.has_all_roles(account, &[burner, MINTER]));
+        assert_eq!(access_control.ensure_any_role(account, &[burner, MINTER]), Ok(()));
+        assert_eq!(
+            access_control.ensure_any_role(account, &[]),
+            Err(AccessControlError::EmptyRoles)
+        );
+        assert_eq!(
+            access_control.ensure_all_roles(account, &[MINTER, burner]),
+            Err(AccessControlError::MissingRole {
+                account,
+                role: burner,
+            })
+        );
+    }
+}
@@ -1,52 +1,223 @@
-use ink::{primitives::AccountId, prelude::vec, prelude::vec::Vec, storage::Mapping};
+use ink::{env::DefaultEnvironment, prelude::vec::Vec, primitives::AccountId, storage::Mapping};
+
+use crate::{
+    expiry::Timestamp, AccessControlError, AccessControlEvents, DefaultAdminRules, Expiry, Role,
+    RoleAdminChanged, RoleGrantCancelled, RoleGranted, RoleId, RoleMask, RoleRevoked,
+};
+
+/// DEFAULT_ADMIN_ROLE is the admin of every role that hasn't been
+/// assigned a different one through `set_role_admin`. Accounts holding
+/// it can grant and revoke any of those roles, including itself.
+pub const DEFAULT_ADMIN_ROLE: usize = 0;
 
 /// AccessControlData encapsulates the process of assigning roles
 /// to accounts and verifying them.
 ///
 /// The generic const `N` represents the static size in bytes of the
-/// inner vector that will be used to store the roles associated with
-/// each account. Each byte is able to store 8 roles.
+/// bitmap that will be used to store the roles associated with each
+/// account. Each byte is able to store 8 roles.
 ///
-/// E.g. AccessControlData<4> allocates inner vectors of 4 bytes,
+/// E.g. AccessControlData<4> stores bitmaps of 4 bytes,
 /// which will be able to store 8 * 4 = 32 roles
+///
+/// The generic const `PAGES` splits the roles of each account in that
+/// many bitmaps of `N` bytes, each stored under its own key, so that
+/// there's no limit on the number of roles and only the page holding
+/// a role has to be read to check it.
+///
+/// E.g. AccessControlData<32, 4> is able to store 8 * 32 * 4 = 1024
+/// roles, roles 0 to 255 living in page 0, 256 to 511 in page 1, etc.
 #[derive(Debug)]
 #[ink::storage_item]
-pub struct AccessControlData<const N: usize> {
-    /// An association between an account_id and the roles it has
-    /// assigned.
+pub struct AccessControlData<const N: usize, const PAGES: usize = 1> {
+    /// An association between an account_id and a page of the roles
+    /// it has assigned.
+    ///
+    /// The roles are stored in bitmaps where each bit acts as a
+    /// role. If that bit is 1 the role is set, otherwise it's unset.
+    /// Pages of an account that never held any of their roles don't
+    /// have an entry.
+    pub roles_per_account: Mapping<(AccountId, u32), BitMap<N>>,
+
+    /// An association between a role and the role that administers
+    /// it, i.e. the role an account needs to grant or revoke it.
     ///
-    /// The roles are stored in a bitmap where each bit of an u128
-    /// acts as a role. If that bit is 1 the role is set, otherwise
-    /// it's unset.
-    pub roles_per_account: Mapping<AccountId, BitMap>,
+    /// Roles without an entry are administered by
+    /// `DEFAULT_ADMIN_ROLE`.
+    pub admin_roles: Mapping<RoleId, RoleId>,
+
+    /// Whether the accounts holding each role are being indexed. See
+    /// `new_enumerable`.
+    pub enumerable: bool,
+
+    /// The accounts holding each role, keyed by their position in the
+    /// role's index. Only kept when `enumerable` is true.
+    pub role_members: Mapping<(RoleId, u32), AccountId>,
+
+    /// The position of each account in the index of every role it
+    /// holds. Only kept when `enumerable` is true.
+    pub role_member_positions: Mapping<(RoleId, AccountId), u32>,
+
+    /// The number of accounts holding each role. Only kept when
+    /// `enumerable` is true.
+    pub role_member_counts: Mapping<RoleId, u32>,
+
+    /// When the roles granted with `set_role_until` expire. Roles
+    /// without an entry are permanent.
+    pub role_expiries: Mapping<(AccountId, RoleId), Expiry>,
+
+    /// The number of entries in `role_expiries`. While it's 0 the
+    /// queries don't have to look the expiries up.
+    pub timed_grants: u32,
+
+    /// When the grants scheduled with `schedule_grant` that haven't
+    /// been executed yet mature.
+    pub pending_grants: Mapping<(AccountId, RoleId), Timestamp>,
+
+    /// The number of entries in `pending_grants`. While it's 0 the
+    /// queries don't have to look the scheduled grants up.
+    pub pending_count: u32,
+
+    /// The minimum delay of the scheduled grants of each role. Roles
+    /// without an entry can be granted right away.
+    pub grant_delays: Mapping<RoleId, Timestamp>,
+
+    /// The number of entries in `grant_delays`.
+    pub delayed_roles: u32,
+
+    /// The roles that have to be offered with `offer_role` and
+    /// accepted to be granted.
+    pub acceptance_roles: Mapping<RoleId, ()>,
+
+    /// The number of entries in `acceptance_roles`.
+    pub two_step_roles: u32,
+
+    /// The admin that offered each role that hasn't been accepted yet.
+    pub role_offers: Mapping<(AccountId, RoleId), AccountId>,
+
+    /// The state of the default admin rules, if they're enabled. See
+    /// `enable_default_admin_rules`.
+    pub default_admin_rules: Option<DefaultAdminRules>,
+
+    /// The roles suspended with `suspend_role`, in pages like the roles
+    /// of each account. Pages without suspended roles don't have an
+    /// entry.
+    pub suspended_roles: Mapping<u32, BitMap<N>>,
+
+    /// The number of suspended roles. While it's 0 the queries don't
+    /// have to look the suspended roles up.
+    pub suspended_count: u32,
+
+    /// The role required to freeze and unfreeze accounts, and to
+    /// suspend and resume roles.
+    pub guardian_role: RoleId,
+
+    /// The accounts frozen with `freeze_account`, along with the block
+    /// timestamp at which each freeze is lifted, if any.
+    pub frozen_accounts: Mapping<AccountId, Option<Timestamp>>,
+
+    /// The number of entries in `frozen_accounts`. While it's 0 the
+    /// queries don't have to look the frozen accounts up.
+    pub frozen_count: u32,
+
+    /// The number of times each role was revoked from every account
+    /// with `revoke_role_from_all`. Roles never revoked that way don't
+    /// have an entry.
+    pub role_generations: Mapping<RoleId, u32>,
+
+    /// The generation of its role each grant was made in, if it's not
+    /// the first one. Grants made in an older generation than the
+    /// current one of their role don't count.
+    pub grant_generations: Mapping<(AccountId, RoleId), u32>,
+
+    /// The number of entries in `role_generations`. While it's 0 the
+    /// queries don't have to look the generations up.
+    pub revoked_roles: u32,
 }
 
+/// BitMap is a fixed-size set of `N * 8` bits, numbered from 0 to
+/// `capacity() - 1`, where bit `pos` is bit `pos % 8` of byte
+/// `pos / 8`.
+///
+/// Being an array, it's encoded as its `N` bytes as they are, without
+/// the length prefix a vector would need, and decoded without
+/// allocating.
+///
+/// The operations on a single bit panic if the position is out of
+/// range, their `try_` versions fail with `RoleOutOfRange` instead:
+///
+/// ```
+/// use access_control::BitMap;
+///
+/// let mut admins = BitMap::<1>::new();
+/// admins.set_bit(0).set_bit(3);
+///
+/// let mut minters = BitMap::<1>::new();
+/// minters.set_bit(3).set_bit(5);
+///
+/// assert_eq!(admins.union(&minters).iter_ones().collect::<Vec<_>>(), [0, 3, 5]);
+/// assert_eq!(admins.intersection(&minters).count_ones(), 1);
+/// assert!(admins.try_set_bit(8).is_err());
+/// ```
 #[repr(transparent)]
-#[derive(Debug, Clone, scale::Encode, scale::Decode)]
-#[cfg_attr(
-    feature = "std",
-    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
-)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct BitMap<const N: usize>([u8; N]);
+
 // NOTE:
 //
-// Once we switch to ink! 5 we'll be able to replace the vector
-// with a const generic slice. The issue is that StorageLayout
-// is not implemented for [T; N] until ink! 5. Tried to implement
-// it manually but couldn't get past the issues with std.
+// ink! 4 only implements StorageLayout for arrays of up to 32
+// elements, one length at a time, so it has to be written by hand for
+// the const generic array. It's laid out the same way ink! lays out
+// those arrays.
 //
 // Related info:
 // - https://github.com/paritytech/ink/pull/1787
 // - https://github.com/paritytech/ink/issues/1785
-pub struct BitMap(Vec<u8>);
+#[cfg(feature = "std")]
+impl<const N: usize> ink::storage::traits::StorageLayout for BitMap<N> {
+    fn layout(key: &ink::primitives::Key) -> ink::metadata::layout::Layout {
+        use ink::metadata::layout::{ArrayLayout, Layout, LayoutKey};
+
+        Layout::Array(ArrayLayout::new(
+            LayoutKey::from(key),
+            N as u32,
+            <u8 as ink::storage::traits::StorageLayout>::layout(key),
+        ))
+    }
+}
+
+impl<const N: usize> Default for BitMap<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> BitMap<N> {
+    /// new returns a bitmap with all of its bits set to 0
+    pub const fn new() -> Self {
+	BitMap([0u8; N])
+    }
+
+    /// from_bytes returns the bitmap made of `bytes`
+    pub const fn from_bytes(bytes: [u8; N]) -> Self {
+        BitMap(bytes)
+    }
 
-impl BitMap {
-    pub fn new(sz: usize) -> Self {
-	BitMap(vec![0u8; sz])
+    /// as_bytes returns the bytes the bitmap is made of
+    pub const fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    #[inline]
+    /// capacity returns the number of bits of the bitmap
+    pub const fn capacity(&self) -> usize {
+        N * 8
     }
 
     #[inline]
     /// set_bit changes the bit at `pos` to 1
-    pub fn set_bit(&mut self, pos: usize) -> &mut Self {
+    pub const fn set_bit(&mut self, pos: usize) -> &mut Self {
 	let idx = pos / 8;
 	let off = pos % 8;
         self.0[idx] |= 1u8 << off;
@@ -55,7 +226,7 @@ impl BitMap {
 
     #[inline]
     /// clear_bit changes the bit at `pos` to 0
-    pub fn clear_bit(&mut self, pos: usize) -> &mut Self {
+    pub const fn clear_bit(&mut self, pos: usize) -> &mut Self {
 	let idx = pos / 8;
 	let off = pos % 8;
         self.0[idx] &= !(1u8 << off);
@@ -64,67 +235,754 @@ impl BitMap {
 
     #[inline]
     /// has_bit_set returns true if the bit at `pos` is 1, false otherwise
-    pub fn has_bit_set(&self, pos: usize) -> bool {
+    pub const fn has_bit_set(&self, pos: usize) -> bool {
 	let idx = pos / 8;
 	let off = pos % 8;
         (self.0[idx] & (1 << off)) > 0
     }
+
+    #[inline]
+    /// is_empty returns true if none of the bits is 1
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|byte| *byte == 0)
+    }
+
+    #[inline]
+    /// count_ones returns the number of bits that are 1
+    pub fn count_ones(&self) -> u32 {
+        self.0.iter().map(|byte| byte.count_ones()).sum()
+    }
+
+    /// iter_ones returns the positions of the bits that are 1, in
+    /// ascending order
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(_, byte)| **byte != 0)
+            .flat_map(|(idx, byte)| {
+                (0..8)
+                    .filter(move |off| byte & (1 << off) != 0)
+                    .map(move |off| idx * 8 + off)
+            })
+    }
+
+    #[inline]
+    /// union returns the bits that are 1 in either bitmap
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    #[inline]
+    /// intersection returns the bits that are 1 in both bitmaps
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    #[inline]
+    /// difference returns the bits that are 1 in the bitmap but not in
+    /// `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    #[inline]
+    /// contains_any returns true if any of the bits that are 1 in
+    /// `other` is 1 in the bitmap too
+    pub fn contains_any(&self, other: &Self) -> bool {
+        self.0.iter().zip(other.0.iter()).any(|(a, b)| a & b != 0)
+    }
+
+    #[inline]
+    /// contains_all returns true if all the bits that are 1 in `other`
+    /// are 1 in the bitmap too
+    pub fn contains_all(&self, other: &Self) -> bool {
+        self.0.iter().zip(other.0.iter()).all(|(a, b)| a & b == *b)
+    }
+
+    #[inline]
+    /// try_set_bit is the checked version of set_bit, it fails with
+    /// `RoleOutOfRange` instead of panicking if `pos` doesn't fit in
+    /// the bitmap
+    pub fn try_set_bit(&mut self, pos: usize) -> Result<&mut Self, AccessControlError> {
+        self.check_pos(pos)?;
+        Ok(self.set_bit(pos))
+    }
+
+    #[inline]
+    /// try_clear_bit is the checked version of clear_bit
+    pub fn try_clear_bit(&mut self, pos: usize) -> Result<&mut Self, AccessControlError> {
+        self.check_pos(pos)?;
+        Ok(self.clear_bit(pos))
+    }
+
+    #[inline]
+    /// try_has_bit_set is the checked version of has_bit_set
+    pub fn try_has_bit_set(&self, pos: usize) -> Result<bool, AccessControlError> {
+        self.check_pos(pos)?;
+        Ok(self.has_bit_set(pos))
+    }
+
+    #[inline]
+    fn zip_with(&self, other: &Self, f: impl Fn(u8, u8) -> u8) -> Self {
+        let mut res = *self;
+
+        for (byte, other) in res.0.iter_mut().zip(other.0.iter()) {
+            *byte = f(*byte, *other);
+        }
+
+        res
+    }
+
+    #[inline]
+    fn check_pos(&self, pos: usize) -> Result<(), AccessControlError> {
+        if pos >= N * 8 {
+            return Err(AccessControlError::RoleOutOfRange);
+        }
+
+        Ok(())
+    }
 }
 
-impl<const N: usize> AccessControlData<N> {
+impl<const N: usize, const PAGES: usize> Default for AccessControlData<N, PAGES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, const PAGES: usize> AccessControlData<N, PAGES> {
     pub fn new() -> Self {
 	const { assert!(N <= 32, "N generic const can't be greater than 32"); }
+	const { assert!(PAGES > 0, "PAGES generic const can't be 0"); }
 
 	AccessControlData {
-	    roles_per_account: Mapping::new()
+	    roles_per_account: Mapping::new(),
+	    admin_roles: Mapping::new(),
+	    enumerable: false,
+	    role_members: Mapping::new(),
+	    role_member_positions: Mapping::new(),
+	    role_member_counts: Mapping::new(),
+	    role_expiries: Mapping::new(),
+	    timed_grants: 0,
+	    pending_grants: Mapping::new(),
+	    pending_count: 0,
+	    grant_delays: Mapping::new(),
+	    delayed_roles: 0,
+	    acceptance_roles: Mapping::new(),
+	    two_step_roles: 0,
+	    role_offers: Mapping::new(),
+	    default_admin_rules: None,
+	    suspended_roles: Mapping::new(),
+	    suspended_count: 0,
+	    guardian_role: DEFAULT_ADMIN_ROLE as RoleId,
+	    frozen_accounts: Mapping::new(),
+	    frozen_count: 0,
+	    role_generations: Mapping::new(),
+	    grant_generations: Mapping::new(),
+	    revoked_roles: 0,
 	}
     }
 
-    pub fn set_role(&mut self, account_id: AccountId, role: usize) {
-        let account_roles = self
-            .roles_per_account
-            .get(account_id)
-            .map_or_else(|| {
-		let mut bm = BitMap::new(N);
-		bm.set_bit(role);
-		bm
-	    }, |roles| {
-		let mut bm = roles.clone();
-		bm.set_bit(role);
-		bm
-	    });
-
-        self.roles_per_account.insert(account_id, &account_roles);
-    }
-
-    pub fn unset_role(&mut self, account_id: AccountId, role: usize) {
-        let account_roles = self
-            .roles_per_account
-            .get(account_id)
-            .map_or(BitMap::new(N), |roles| {
-		let mut bm = roles.clone();
-		bm.clear_bit(role);
-		bm
-	    });
+    /// new_enumerable works like `new` but also keeps an index of the
+    /// accounts holding each role, so that they can be listed with
+    /// `get_role_member` and `role_members`.
+    ///
+    /// Keeping the index costs extra storage writes on every grant and
+    /// revocation, so it's only worth it if the contract needs to
+    /// answer who holds a role on-chain.
+    pub fn new_enumerable() -> Self {
+        AccessControlData {
+            enumerable: true,
+            ..Self::new()
+        }
+    }
+
+    /// set_role grants `role` to `account_id` without any
+    /// authorization, emitting `RoleGranted` through `E` if the account
+    /// didn't hold it yet. The sender of the event is the caller of
+    /// the contract.
+    ///
+    /// It panics on `DEFAULT_ADMIN_ROLE` under the default admin rules.
+    pub fn set_role<E: AccessControlEvents>(&mut self, account_id: AccountId, role: impl Role) {
+        let role = Self::index(role);
+        self.assert_default_admin_rules(role);
+        self.set_role_by::<E>(Self::caller(), account_id, role);
+    }
 
-        self.roles_per_account.insert(account_id, &account_roles);
+    /// unset_role revokes `role` from `account_id` without any
+    /// authorization, emitting `RoleRevoked` through `E` if the account
+    /// held it.
+    ///
+    /// It panics on `DEFAULT_ADMIN_ROLE` under the default admin rules.
+    pub fn unset_role<E: AccessControlEvents>(&mut self, account_id: AccountId, role: impl Role) {
+        let role = Self::index(role);
+        self.assert_default_admin_rules(role);
+        self.unset_role_by::<E>(Self::caller(), account_id, role);
     }
 
-    pub fn has_role(&self, account_id: AccountId, role: usize) -> bool {
-        match self.roles_per_account.get(account_id) {
-            Some(curr_roles) => curr_roles.has_bit_set(role),
+    /// has_role returns true if `account_id` holds `role`. Roles out
+    /// of range, whose grant expired, whose scheduled grant didn't
+    /// mature yet or that were revoked from every account since they
+    /// were granted are never held, and frozen accounts hold no role.
+    pub fn has_role(&self, account_id: AccountId, role: impl Role) -> bool {
+        let role = Self::index(role);
+
+        if Self::check_role(role).is_err() {
+            return false;
+        }
+
+        let (page, bit) = Self::page_of(role);
+        match self.roles_per_account.get((account_id, page)) {
+            Some(curr_roles) => {
+                curr_roles.has_bit_set(bit)
+                    && !self.is_inactive(account_id, role)
+                    && !self.is_role_suspended(role)
+                    && !self.is_frozen(account_id)
+            }
             None => false,
         }
     }
+
+    /// has_any_role returns true if `account_id` holds any of `roles`.
+    ///
+    /// Each page is read once however many of `roles` live in it, so
+    /// with a single page it costs a single storage read.
+    pub fn has_any_role(&self, account_id: AccountId, roles: &[impl Role]) -> bool {
+        // roles out of range are never held, so they're left out
+        let mask = roles
+            .iter()
+            .fold(RoleMask::new(), |mask, role| mask.try_with(*role).unwrap_or(mask));
+
+        self.has_any_of(account_id, &mask)
+    }
+
+    /// has_all_roles returns true if `account_id` holds all of `roles`,
+    /// reading each page once like `has_any_role`
+    pub fn has_all_roles(&self, account_id: AccountId, roles: &[impl Role]) -> bool {
+        match RoleMask::try_from_roles(roles) {
+            Ok(mask) => self.has_all_of(account_id, &mask),
+            Err(_) => false,
+        }
+    }
+
+    /// has_any_of returns true if `account_id` holds any of the roles
+    /// in `mask`. Only the pages where `mask` has roles are read, and
+    /// reading stops at the first one holding any of them.
+    pub fn has_any_of(&self, account_id: AccountId, mask: &RoleMask<N, PAGES>) -> bool {
+        self.pages_of(account_id, mask)
+            .any(|(page, account_roles, roles)| {
+                account_roles
+                    .intersection(roles)
+                    .iter_ones()
+                    .any(|bit| !self.is_inactive(account_id, page * N * 8 + bit))
+            })
+    }
+
+    /// has_all_of returns true if `account_id` holds all the roles in
+    /// `mask`. Only the pages where `mask` has roles are read, and
+    /// reading stops at the first one missing any of them.
+    pub fn has_all_of(&self, account_id: AccountId, mask: &RoleMask<N, PAGES>) -> bool {
+        self.pages_of(account_id, mask)
+            .all(|(page, account_roles, roles)| {
+                account_roles.contains_all(roles)
+                    && roles
+                        .iter_ones()
+                        .all(|bit| !self.is_inactive(account_id, page * N * 8 + bit))
+            })
+    }
+
+    /// role_mask_of returns every role `account_id` holds, reading
+    /// each of its pages once
+    pub fn role_mask_of(&self, account_id: AccountId) -> RoleMask<N, PAGES> {
+        let mut mask = RoleMask::new();
+
+        if self.is_frozen(account_id) {
+            return mask;
+        }
+
+        for (page, roles) in mask.pages.iter_mut().enumerate() {
+            let Some(account_roles) = self.roles_per_account.get((account_id, page as u32)) else {
+                continue;
+            };
+
+            *roles = self.without_suspended(page as u32, account_roles);
+
+            if self.may_be_inactive() {
+                for bit in account_roles.iter_ones() {
+                    if self.is_inactive(account_id, page * N * 8 + bit) {
+                        roles.clear_bit(bit);
+                    }
+                }
+            }
+        }
+
+        mask
+    }
+
+    /// roles_of returns the bit positions of every role `account_id`
+    /// holds, in ascending order
+    pub fn roles_of(&self, account_id: AccountId) -> Vec<usize> {
+        self.role_mask_of(account_id).iter().collect()
+    }
+
+    /// roles_of_as is roles_of for roles of type `R`, such as enums
+    /// deriving `Role`. Roles held at bit positions that aren't an `R`
+    /// are left out.
+    pub fn roles_of_as<R: Role + TryFrom<usize>>(&self, account_id: AccountId) -> Vec<R> {
+        self.role_mask_of(account_id)
+            .iter()
+            .filter_map(|role| R::try_from(role).ok())
+            .collect()
+    }
+
+    /// try_set_role is the checked version of set_role, it fails with
+    /// `RoleOutOfRange` if `role` doesn't fit in `PAGES` pages of `N`
+    /// bytes, and with `EnforcedDefaultAdminRules` if it's
+    /// `DEFAULT_ADMIN_ROLE` under the default admin rules
+    pub fn try_set_role<E: AccessControlEvents>(
+        &mut self,
+        account_id: AccountId,
+        role: impl Role,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+        Self::check_role(role)?;
+        self.check_default_admin_rules(role)?;
+        self.set_role::<E>(account_id, role);
+        Ok(())
+    }
+
+    /// try_unset_role is the checked version of unset_role
+    pub fn try_unset_role<E: AccessControlEvents>(
+        &mut self,
+        account_id: AccountId,
+        role: impl Role,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+        Self::check_role(role)?;
+        self.check_default_admin_rules(role)?;
+        self.unset_role::<E>(account_id, role);
+        Ok(())
+    }
+
+    /// ensure_role fails with `MissingRole` if `account_id` doesn't
+    /// hold `role`. It's meant to guard privileged messages:
+    ///
+    /// ```ignore
+    /// self.access_control.ensure_role(self.env().caller(), Self::ROLE)?;
+    /// ```
+    pub fn ensure_role(
+        &self,
+        account_id: AccountId,
+        role: impl Role,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+        Self::check_role(role)?;
+
+        if !self.has_role(account_id, role) {
+            return Err(AccessControlError::MissingRole {
+                account: account_id,
+                role:    role as RoleId,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// ensure_any_role fails with `MissingRole` for the first of
+    /// `roles` if `account_id` doesn't hold any of them, and with
+    /// `EmptyRoles` if there are none, like `has_any_role` returns
+    /// false for them
+    pub fn ensure_any_role(
+        &self,
+        account_id: AccountId,
+        roles: &[impl Role],
+    ) -> Result<(), AccessControlError> {
+        if self.has_any_role(account_id, roles) {
+            return Ok(());
+        }
+
+        match roles.first() {
+            Some(role) => self.ensure_role(account_id, *role),
+            None => Err(AccessControlError::EmptyRoles),
+        }
+    }
+
+    /// ensure_all_roles fails with `MissingRole` for the first of
+    /// `roles` that `account_id` doesn't hold
+    pub fn ensure_all_roles(
+        &self,
+        account_id: AccountId,
+        roles: &[impl Role],
+    ) -> Result<(), AccessControlError> {
+        if self.has_all_roles(account_id, roles) {
+            return Ok(());
+        }
+
+        roles
+            .iter()
+            .try_for_each(|role| self.ensure_role(account_id, *role))
+    }
+
+    /// get_role_admin returns the index of the role that administers
+    /// `role`
+    pub fn get_role_admin(&self, role: impl Role) -> usize {
+        self.admin_roles
+            .get(Self::index(role) as RoleId)
+            .map_or(DEFAULT_ADMIN_ROLE, |admin_role| admin_role as usize)
+    }
+
+    /// set_role_admin makes `admin_role` the admin of `role` and emits
+    /// `RoleAdminChanged` through `E`.
+    ///
+    /// Like `set_role` it doesn't perform any authorization, it's
+    /// meant to be used while setting up the contract or behind the
+    /// contract's own checks.
+    pub fn set_role_admin<E: AccessControlEvents>(
+        &mut self,
+        role: impl Role,
+        admin_role: impl Role,
+    ) {
+        let (role, admin_role) = (Self::index(role), Self::index(admin_role));
+        let previous_admin_role = self.get_role_admin(role);
+
+        if admin_role == DEFAULT_ADMIN_ROLE {
+            self.admin_roles.remove(role as RoleId);
+        } else {
+            self.admin_roles.insert(role as RoleId, &(admin_role as RoleId));
+        }
+
+        E::emit_role_admin_changed(RoleAdminChanged {
+            role:                role as RoleId,
+            previous_admin_role: previous_admin_role as RoleId,
+            new_admin_role:      admin_role as RoleId,
+        });
+    }
+
+    /// grant_role sets `role` to `account_id` if `caller` holds the
+    /// admin role of `role`
+    pub fn grant_role<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        account_id: AccountId,
+        role: impl Role,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+        Self::check_role(role)?;
+        self.check_role_admin(caller, role)?;
+        self.check_direct_grant(role)?;
+        self.set_role_by::<E>(caller, account_id, role);
+        Ok(())
+    }
+
+    /// revoke_role unsets `role` from `account_id` if `caller` holds
+    /// the admin role of `role`
+    pub fn revoke_role<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        account_id: AccountId,
+        role: impl Role,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+        Self::check_role(role)?;
+        self.check_role_admin(caller, role)?;
+        self.unset_role_by::<E>(caller, account_id, role);
+        Ok(())
+    }
+
+    /// renounce_role unsets `role` from `caller`. `account_id` must be
+    /// equal to `caller`, so an account can only renounce its own
+    /// roles.
+    pub fn renounce_role<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        account_id: AccountId,
+        role: impl Role,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+
+        if caller != account_id {
+            return Err(AccessControlError::BadConfirmation);
+        }
+
+        Self::check_role(role)?;
+        self.check_default_admin_rules(role)?;
+        self.unset_role_by::<E>(caller, account_id, role);
+        Ok(())
+    }
+
+    pub(crate) fn set_role_by<E: AccessControlEvents>(
+        &mut self,
+        sender: AccountId,
+        account_id: AccountId,
+        role: usize,
+    ) {
+        let (page, bit) = Self::page_of(role);
+        assert!(
+            self.check_default_admin_grant(account_id, role).is_ok(),
+            "only one account can hold the default admin role"
+        );
+        let mut account_roles = self.clear_stale(account_id, page);
+
+        if account_roles.has_bit_set(bit) {
+            self.regrant::<E>(sender, account_id, role);
+            return;
+        }
+
+        account_roles.set_bit(bit);
+        self.roles_per_account.insert((account_id, page), &account_roles);
+        self.role_changed::<E>(sender, account_id, role, true);
+    }
+
+    pub(crate) fn unset_role_by<E: AccessControlEvents>(
+        &mut self,
+        sender: AccountId,
+        account_id: AccountId,
+        role: usize,
+    ) {
+        let (page, bit) = Self::page_of(role);
+        let mut account_roles = self.clear_stale(account_id, page);
+
+        if !account_roles.has_bit_set(bit) {
+            return;
+        }
+
+        account_roles.clear_bit(bit);
+        self.store_page(account_id, page, &account_roles);
+        self.role_changed::<E>(sender, account_id, role, false);
+    }
+
+    /// regrant handles granting a role that's already in the bitmap of
+    /// `account_id`: it makes it permanent, if it was scheduled it
+    /// takes effect right away, and if it's `DEFAULT_ADMIN_ROLE` held
+    /// from before the default admin rules the account becomes the
+    /// default admin
+    pub(crate) fn regrant<E: AccessControlEvents>(
+        &mut self,
+        sender: AccountId,
+        account_id: AccountId,
+        role: usize,
+    ) {
+        if self.is_displaced_admin(account_id, role) {
+            self.default_admin_changed(account_id, true);
+        }
+
+        self.clear_expiry(account_id, role);
+
+        if self.take_pending_grant(account_id, role) {
+            self.role_changed::<E>(sender, account_id, role, true);
+        }
+    }
+
+    /// role_changed keeps the members index up to date and emits the
+    /// event for a role that was just granted or revoked. Revoking a
+    /// role whose scheduled grant didn't mature yet cancels the grant
+    /// instead, while a matured one is revoked like any other.
+    pub(crate) fn role_changed<E: AccessControlEvents>(
+        &mut self,
+        sender: AccountId,
+        account_id: AccountId,
+        role: usize,
+        granted: bool,
+    ) {
+        if role == DEFAULT_ADMIN_ROLE {
+            self.default_admin_changed(account_id, granted);
+        }
+
+        if granted {
+            self.record_grant(account_id, role);
+
+            if self.enumerable {
+                self.add_role_member(role as RoleId, account_id);
+            }
+
+            E::emit_role_granted(RoleGranted {
+                role: role as RoleId,
+                account: account_id,
+                sender,
+            });
+        } else {
+            self.clear_expiry(account_id, role);
+            self.forget_grant(account_id, role);
+
+            let pending = self.is_pending(account_id, role);
+            if self.take_pending_grant(account_id, role) && pending {
+                E::emit_role_grant_cancelled(RoleGrantCancelled {
+                    role: role as RoleId,
+                    account: account_id,
+                    sender,
+                });
+                return;
+            }
+
+            if self.enumerable {
+                self.remove_role_member(role as RoleId, account_id);
+            }
+
+            E::emit_role_revoked(RoleRevoked {
+                role: role as RoleId,
+                account: account_id,
+                sender,
+            });
+        }
+    }
+
+    /// pages_of lazily reads the pages of the roles of `account_id`
+    /// where `mask` has any role, along with the page number and the
+    /// same page of `mask`. The pages of frozen accounts are empty.
+    fn pages_of<'a>(
+        &'a self,
+        account_id: AccountId,
+        mask: &'a RoleMask<N, PAGES>,
+    ) -> impl Iterator<Item = (usize, BitMap<N>, &'a BitMap<N>)> + 'a {
+        let frozen = self.is_frozen(account_id);
+
+        mask.pages
+            .iter()
+            .enumerate()
+            .filter(|(_, roles)| !roles.is_empty())
+            .map(move |(page, roles)| {
+                if frozen {
+                    return (page, BitMap::default(), roles);
+                }
+
+                let account_roles = self
+                    .roles_per_account
+                    .get((account_id, page as u32))
+                    .unwrap_or_default();
+
+                (page, self.without_suspended(page as u32, account_roles), roles)
+            })
+    }
+
+    /// is_inactive returns true if the grant of `role` to `account_id`
+    /// expired, is scheduled and didn't mature yet, was revoked with
+    /// the rest of grants of `role`, or is a grant of
+    /// `DEFAULT_ADMIN_ROLE` to an account other than the default admin
+    pub(crate) fn is_inactive(&self, account_id: AccountId, role: usize) -> bool {
+        self.is_expired(account_id, role)
+            || self.is_pending(account_id, role)
+            || self.is_stale(account_id, role)
+            || self.is_displaced_admin(account_id, role)
+    }
+
+    /// may_be_inactive returns false if no role in the bitmaps can be
+    /// inactive, so that queries can skip checking each of them
+    pub(crate) fn may_be_inactive(&self) -> bool {
+        self.timed_grants > 0
+            || self.pending_count > 0
+            || self.revoked_roles > 0
+            || self.default_admin_rules.is_some()
+    }
+
+    /// store_page writes a page of the roles of `account_id`, removing
+    /// it if it's empty so that its storage deposit is refunded
+    pub(crate) fn store_page(&mut self, account_id: AccountId, page: u32, roles: &BitMap<N>) {
+        if roles.is_empty() {
+            self.roles_per_account.remove((account_id, page));
+        } else {
+            self.roles_per_account.insert((account_id, page), roles);
+        }
+    }
+
+    pub(crate) fn caller() -> AccountId {
+        ink::env::caller::<DefaultEnvironment>()
+    }
+
+    /// check_role_admin fails unless `caller` can grant and revoke
+    /// `role`, which takes holding its admin role, and `role` not being
+    /// `DEFAULT_ADMIN_ROLE` under the default admin rules
+    pub(crate) fn check_role_admin(
+        &self,
+        caller: AccountId,
+        role: usize,
+    ) -> Result<(), AccessControlError> {
+        self.check_default_admin_rules(role)?;
+        self.ensure_role(caller, self.get_role_admin(role))
+    }
+
+    /// index returns the bit position of `role`, failing to compile if
+    /// `R` has more roles than the ones that fit in `PAGES` pages of
+    /// `N` bytes
+    pub(crate) fn index<R: Role>(role: R) -> usize {
+        const {
+            assert!(
+                R::COUNT <= N * 8 * PAGES,
+                "the role type has more roles than AccessControlData can store"
+            );
+        }
+
+        role.index()
+    }
+
+    /// page_of returns the page holding `role` and its bit position
+    /// within the page, panicking if `role` is out of range like the
+    /// unchecked operations on a single bitmap do
+    pub(crate) fn page_of(role: usize) -> (u32, usize) {
+        assert!(role < N * 8 * PAGES, "role out of range");
+
+        ((role / (N * 8)) as u32, role % (N * 8))
+    }
+
+    pub(crate) fn check_role(role: usize) -> Result<(), AccessControlError> {
+        if role >= N * 8 * PAGES {
+            return Err(AccessControlError::RoleOutOfRange);
+        }
+
+        Ok(())
+    }
+
+    /// check_direct_grant fails if `role` can't be granted right away
+    /// by its admins, because it has a grant delay or requires
+    /// acceptance
+    pub(crate) fn check_direct_grant(&self, role: usize) -> Result<(), AccessControlError> {
+        self.check_grant_delay(role)?;
+        self.check_acceptance(role)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::RefCell;
+
+    #[derive(Debug, PartialEq)]
+    enum Emitted {
+        Granted(RoleGranted),
+        Revoked(RoleRevoked),
+        AdminChanged(RoleAdminChanged),
+    }
+
+    std::thread_local! {
+        static EMITTED: RefCell<Vec<Emitted>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Recorder keeps the events emitted by AccessControlData so that
+    /// the tests can inspect them
+    struct Recorder;
+
+    impl Recorder {
+        fn take() -> Vec<Emitted> {
+            EMITTED.with(|emitted| emitted.take())
+        }
+    }
+
+    impl AccessControlEvents for Recorder {
+        fn emit_role_granted(event: RoleGranted) {
+            EMITTED.with(|emitted| emitted.borrow_mut().push(Emitted::Granted(event)));
+        }
+
+        fn emit_role_revoked(event: RoleRevoked) {
+            EMITTED.with(|emitted| emitted.borrow_mut().push(Emitted::Revoked(event)));
+        }
+
+        fn emit_role_admin_changed(event: RoleAdminChanged) {
+            EMITTED.with(|emitted| emitted.borrow_mut().push(Emitted::AdminChanged(event)));
+        }
+    }
 
     #[test]
     fn test_bitmap_set_bit() {
-        let mut bm = BitMap([0u8; 4].into());
+        let mut bm = BitMap([0u8; 4]);
 
         bm.set_bit(0).set_bit(1).set_bit(31);
 
@@ -136,7 +994,7 @@ mod tests {
 
     #[test]
     fn test_bitmap_clear_bit() {
-        let mut bm = BitMap([u8::MAX; 4].into());
+        let mut bm = BitMap([u8::MAX; 4]);
 
         bm.clear_bit(0).clear_bit(1).clear_bit(31);
 
@@ -148,12 +1006,73 @@ mod tests {
 
     #[test]
     fn test_bitmap_has_bit_set() {
-        let bm = BitMap([6, 2, 0, 0].into());
+        let bm = BitMap([6, 2, 0, 0]);
 
-        assert_eq!(bm.has_bit_set(0), false);
-        assert_eq!(bm.has_bit_set(1), true);
-        assert_eq!(bm.has_bit_set(2), true);
-        assert_eq!(bm.has_bit_set(9), true);
+        assert!(!bm.has_bit_set(0));
+        assert!(bm.has_bit_set(1));
+        assert!(bm.has_bit_set(2));
+        assert!(bm.has_bit_set(9));
+    }
+
+    #[test]
+    fn test_bitmap_checked_ops_fail_out_of_range() {
+        let mut bm = BitMap::<4>::new();
+
+        assert_eq!(bm.try_set_bit(32).err(), Some(AccessControlError::RoleOutOfRange));
+        assert_eq!(bm.try_clear_bit(32).err(), Some(AccessControlError::RoleOutOfRange));
+        assert_eq!(bm.try_has_bit_set(32), Err(AccessControlError::RoleOutOfRange));
+
+        assert!(bm.try_set_bit(31).is_ok());
+        assert_eq!(bm.try_has_bit_set(31), Ok(true));
+    }
+
+    #[test]
+    fn test_bitmap_is_packed() {
+        use scale::Encode;
+
+        // a vector of the same bytes, like the one BitMap used to wrap,
+        // needs an extra byte for its length. The weight of both layouts
+        // is compared on a node by the e2e test of tests/layout.
+        let before = ink::prelude::vec![0u8; 4];
+        let after = BitMap::<4>::new();
+
+        assert_eq!(before.encoded_size(), 5);
+        assert_eq!(after.encoded_size(), 4);
+
+        let mut bm = BitMap::<32>::new();
+        bm.set_bit(0).set_bit(255);
+        assert_eq!(bm.encode().len(), 32);
+        assert_eq!(<BitMap<32> as scale::Decode>::decode(&mut &bm.encode()[..]), Ok(bm));
+    }
+
+    #[test]
+    fn test_bitmap_count_and_iter_ones() {
+        let bm = BitMap([0b1000_0101, 0, 0b0000_0010]);
+
+        assert_eq!(bm.count_ones(), 4);
+        assert_eq!(bm.iter_ones().collect::<Vec<_>>(), [0, 2, 7, 17]);
+        assert_eq!(bm.capacity(), 24);
+
+        assert!(BitMap::<3>::new().is_empty());
+        assert_eq!(BitMap::<3>::new().iter_ones().count(), 0);
+        assert!(!bm.is_empty());
+    }
+
+    #[test]
+    fn test_bitmap_set_algebra() {
+        let a = BitMap::from_bytes([0b0011, 0b0001]);
+        let b = BitMap::from_bytes([0b0110, 0b0000]);
+
+        assert_eq!(a.union(&b).as_bytes(), &[0b0111, 0b0001]);
+        assert_eq!(a.intersection(&b).as_bytes(), &[0b0010, 0b0000]);
+        assert_eq!(a.difference(&b).as_bytes(), &[0b0001, 0b0001]);
+        assert_eq!(b.difference(&a).as_bytes(), &[0b0100, 0b0000]);
+
+        assert!(a.contains_any(&b));
+        assert!(!a.contains_all(&b));
+        assert!(a.contains_all(&a.intersection(&b)));
+        assert!(!a.contains_any(&BitMap::new()));
+        assert!(a.contains_all(&BitMap::new()));
     }
 
     #[ink::test]
@@ -163,12 +1082,12 @@ mod tests {
 	let (r1, r2) = (0, 1);
 
 	// set some roles and check that they have been set
-        access_control.set_role(account, r1);
-        access_control.set_role(account, r2);
+        access_control.set_role::<()>(account, r1);
+        access_control.set_role::<()>(account, r2);
 
         let roles = access_control
             .roles_per_account
-            .get(account)
+            .get((account, 0))
             .unwrap_or_else(|| panic!());
 
         assert_eq!(roles.0, [3, 0, 0, 0]);
@@ -181,25 +1100,49 @@ mod tests {
 	let (r1, r2, r3, r4) = (0, 1, 2, 8);
 
 	// set some roles for testing
-        access_control.set_role(account, r1);
-        access_control.set_role(account, r2);
-        access_control.set_role(account, r3);
+        access_control.set_role::<()>(account, r1);
+        access_control.set_role::<()>(account, r2);
+        access_control.set_role::<()>(account, r3);
 
 	// unset one of the roles and check that it has been unset
-	access_control.unset_role(account, r2);
+	access_control.unset_role::<()>(account, r2);
 
 	// verify that unset'ing a role that is not set doesn't do
 	// anything weird
-	access_control.unset_role(account, r4);
+	access_control.unset_role::<()>(account, r4);
 
         let roles = access_control
             .roles_per_account
-            .get(account)
+            .get((account, 0))
             .unwrap_or_else(|| panic!());
 
         assert_eq!(roles.0, [5, 0, 0, 0]);
     }
 
+    #[ink::test]
+    fn unset_role_removes_empty_entries() {
+        let mut access_control = AccessControlData::<4>::new();
+        let account = AccountId::from([1u8; 32]);
+        let stranger = AccountId::from([2u8; 32]);
+        let (r1, r2) = (0, 1);
+
+        // revoking from an account without roles doesn't store
+        // anything
+        access_control.unset_role::<()>(stranger, r1);
+        assert!(!access_control.roles_per_account.contains((stranger, 0)));
+
+        access_control.set_role::<()>(account, r1);
+        access_control.set_role::<()>(account, r2);
+
+        access_control.unset_role::<()>(account, r1);
+        assert!(access_control.roles_per_account.contains((account, 0)));
+
+        // clearing the last role removes the entry
+        access_control.unset_role::<()>(account, r2);
+        assert!(!access_control.roles_per_account.contains((account, 0)));
+        assert!(!access_control.has_role(account, r2));
+    }
+
     #[ink::test]
     fn has_role_works() {
         let mut access_control = AccessControlData::<4>::new();
@@ -207,14 +1150,357 @@ mod tests {
 	let (r1, r2, r3, r4, r5) = (0, 1, 2, 3, 4);
 
 	// set some roles for testing
-        access_control.set_role(account, r1);
-        access_control.set_role(account, r2);
-        access_control.set_role(account, r5);
-
-	assert_eq!(access_control.has_role(account, r1), true);
-	assert_eq!(access_control.has_role(account, r2), true);
-	assert_eq!(access_control.has_role(account, r3), false);
-	assert_eq!(access_control.has_role(account, r4), false);
-	assert_eq!(access_control.has_role(account, r5), true);
+        access_control.set_role::<()>(account, r1);
+        access_control.set_role::<()>(account, r2);
+        access_control.set_role::<()>(account, r5);
+
+	assert!(access_control.has_role(account, r1));
+	assert!(access_control.has_role(account, r2));
+	assert!(!access_control.has_role(account, r3));
+	assert!(!access_control.has_role(account, r4));
+	assert!(access_control.has_role(account, r5));
+    }
+
+    #[ink::test]
+    fn get_role_admin_defaults_to_default_admin_role() {
+        let access_control = AccessControlData::<4>::new();
+
+        assert_eq!(access_control.get_role_admin(1), DEFAULT_ADMIN_ROLE);
+        assert_eq!(access_control.get_role_admin(DEFAULT_ADMIN_ROLE), DEFAULT_ADMIN_ROLE);
+    }
+
+    #[ink::test]
+    fn set_role_admin_works() {
+        let mut access_control = AccessControlData::<4>::new();
+        let (role, admin_role) = (1, 2);
+
+        access_control.set_role_admin::<()>(role, admin_role);
+        assert_eq!(access_control.get_role_admin(role), admin_role);
+
+        // going back to the default admin shouldn't leave an entry
+        // behind
+        access_control.set_role_admin::<()>(role, DEFAULT_ADMIN_ROLE);
+        assert_eq!(access_control.get_role_admin(role), DEFAULT_ADMIN_ROLE);
+        assert!(!access_control.admin_roles.contains(role as RoleId));
+    }
+
+    #[ink::test]
+    fn grant_and_revoke_role_by_admin_works() {
+        let mut access_control = AccessControlData::<4>::new();
+        let admin = AccountId::from([1u8; 32]);
+        let account = AccountId::from([2u8; 32]);
+        let role = 1;
+
+        access_control.set_role::<()>(admin, DEFAULT_ADMIN_ROLE);
+
+        assert_eq!(access_control.grant_role::<()>(admin, account, role), Ok(()));
+        assert!(access_control.has_role(account, role));
+
+        assert_eq!(access_control.revoke_role::<()>(admin, account, role), Ok(()));
+        assert!(!access_control.has_role(account, role));
+    }
+
+    #[ink::test]
+    fn grant_and_revoke_role_by_non_admin_fails() {
+        let mut access_control = AccessControlData::<4>::new();
+        let account = AccountId::from([1u8; 32]);
+        let other = AccountId::from([2u8; 32]);
+        let role = 1;
+
+        let expected = Err(AccessControlError::MissingRole {
+            account,
+            role: DEFAULT_ADMIN_ROLE as RoleId,
+        });
+
+        assert_eq!(access_control.grant_role::<()>(account, other, role), expected);
+        assert!(!access_control.has_role(other, role));
+
+        access_control.set_role::<()>(other, role);
+
+        assert_eq!(access_control.revoke_role::<()>(account, other, role), expected);
+        assert!(access_control.has_role(other, role));
+    }
+
+    #[ink::test]
+    fn custom_role_admin_works() {
+        let mut access_control = AccessControlData::<4>::new();
+        let (admin, manager, account) = (
+            AccountId::from([1u8; 32]),
+            AccountId::from([2u8; 32]),
+            AccountId::from([3u8; 32]),
+        );
+        let (role, manager_role) = (1, 2);
+
+        access_control.set_role::<()>(admin, DEFAULT_ADMIN_ROLE);
+        access_control.set_role::<()>(manager, manager_role);
+        access_control.set_role_admin::<()>(role, manager_role);
+
+        // the default admin no longer administers `role`...
+        assert_eq!(
+            access_control.grant_role::<()>(admin, account, role),
+            Err(AccessControlError::MissingRole {
+                account: admin,
+                role:    manager_role as RoleId,
+            })
+        );
+
+        // ...but the manager does
+        assert_eq!(access_control.grant_role::<()>(manager, account, role), Ok(()));
+        assert!(access_control.has_role(account, role));
+    }
+
+    #[ink::test]
+    fn renounce_role_works() {
+        let mut access_control = AccessControlData::<4>::new();
+        let account = AccountId::from([1u8; 32]);
+        let other = AccountId::from([2u8; 32]);
+        let role = 1;
+
+        access_control.set_role::<()>(account, role);
+        access_control.set_role::<()>(other, role);
+
+        // renouncing on behalf of someone else isn't allowed
+        assert_eq!(
+            access_control.renounce_role::<()>(account, other, role),
+            Err(AccessControlError::BadConfirmation)
+        );
+        assert!(access_control.has_role(other, role));
+
+        assert_eq!(access_control.renounce_role::<()>(account, account, role), Ok(()));
+        assert!(!access_control.has_role(account, role));
+    }
+
+    #[ink::test]
+    fn checked_role_ops_fail_out_of_range() {
+        let mut access_control = AccessControlData::<4>::new();
+        let account = AccountId::from([1u8; 32]);
+
+        access_control.set_role::<()>(account, DEFAULT_ADMIN_ROLE);
+
+        let expected = Err(AccessControlError::RoleOutOfRange);
+        assert_eq!(access_control.try_set_role::<()>(account, 32), expected);
+        assert_eq!(access_control.try_unset_role::<()>(account, 32), expected);
+        assert_eq!(access_control.ensure_role(account, 32), expected);
+        assert_eq!(access_control.grant_role::<()>(account, account, 32), expected);
+        assert_eq!(access_control.revoke_role::<()>(account, account, 32), expected);
+
+        // has_role doesn't trap either
+        assert!(!access_control.has_role(account, 32));
+    }
+
+    #[ink::test]
+    fn roles_are_validated_before_the_caller() {
+        let mut access_control = AccessControlData::<4>::new();
+        let (caller, account) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+        let expiry = crate::Expiry::Timestamp(1_000);
+
+        let expected = Err(AccessControlError::RoleOutOfRange);
+        assert_eq!(access_control.grant_role::<()>(caller, account, 32), expected);
+        assert_eq!(access_control.revoke_role::<()>(caller, account, 32), expected);
+        assert_eq!(access_control.grant_role_until::<()>(caller, account, 32, expiry), expected);
+        assert_eq!(access_control.grant_roles::<()>(caller, &[(account, 32)]), expected);
+        assert_eq!(access_control.schedule_grant::<()>(caller, account, 32, 0), expected);
+        assert_eq!(access_control.cancel_grant::<()>(caller, account, 32), expected);
+        assert_eq!(access_control.offer_role::<()>(caller, account, 32), expected);
+        assert_eq!(access_control.withdraw_offer::<()>(caller, account, 32), expected);
+        assert_eq!(access_control.revoke_role_from_all::<()>(caller, 32), expected);
+        assert_eq!(access_control.suspend_role::<()>(caller, 32), expected);
+    }
+
+    #[ink::test]
+    fn paged_roles_work() {
+        let mut access_control = AccessControlData::<1, 4>::new();
+        let account = AccountId::from([1u8; 32]);
+        let (r1, r2, r3) = (1, 17, 31);
+
+        access_control.set_role::<()>(account, r1);
+        access_control.set_role::<()>(account, r2);
+        access_control.set_role::<()>(account, r3);
+
+        assert!(access_control.has_role(account, r1));
+        assert!(access_control.has_role(account, r2));
+        assert!(access_control.has_role(account, r3));
+        assert!(!access_control.has_role(account, 9));
+
+        // each page is stored on its own, and pages without roles
+        // aren't stored at all
+        let page = |page| access_control.roles_per_account.get((account, page)).map(|p| p.0);
+        assert_eq!(page(0), Some([2]));
+        assert_eq!(page(1), None);
+        assert_eq!(page(2), Some([2]));
+        assert_eq!(page(3), Some([128]));
+
+        access_control.unset_role::<()>(account, r2);
+        assert!(!access_control.has_role(account, r2));
+        assert!(access_control.has_role(account, r3));
+
+        // roles past the last page are out of range
+        assert_eq!(
+            access_control.try_set_role::<()>(account, 32),
+            Err(AccessControlError::RoleOutOfRange)
+        );
+        assert!(!access_control.has_role(account, 32));
+    }
+
+    #[ink::test]
+    fn ensure_role_works() {
+        let mut access_control = AccessControlData::<4>::new();
+        let account = AccountId::from([1u8; 32]);
+        let role = 1;
+
+        assert_eq!(
+            access_control.ensure_role(account, role),
+            Err(AccessControlError::MissingRole {
+                account,
+                role: role as RoleId,
+            })
+        );
+
+        access_control.set_role::<()>(account, role);
+        assert_eq!(access_control.ensure_role(account, role), Ok(()));
+    }
+
+    #[ink::test]
+    fn has_any_and_all_roles_work() {
+        let mut access_control = AccessControlData::<1, 2>::new();
+        let account = AccountId::from([1u8; 32]);
+        let (r1, r2, r3, r4) = (1, 2, 9, 10);
+
+        access_control.set_role::<()>(account, r1);
+        access_control.set_role::<()>(account, r3);
+
+        assert!(access_control.has_any_role(account, &[r2, r3]));
+        assert!(!access_control.has_any_role(account, &[r2, r4]));
+        assert!(!access_control.has_any_role(account, &[r2, 16]));
+        assert!(!access_control.has_any_role(account, &[0usize; 0]));
+
+        assert!(access_control.has_all_roles(account, &[r1, r3]));
+        assert!(!access_control.has_all_roles(account, &[r1, r2]));
+        assert!(!access_control.has_all_roles(account, &[r1, 16]));
+        assert!(access_control.has_all_roles(account, &[0usize; 0]));
+
+        const MASK: RoleMask<1, 2> = RoleMask::of(&[1, 9]);
+        assert!(access_control.has_all_of(account, &MASK));
+        assert!(access_control.has_any_of(account, &MASK));
+    }
+
+    #[ink::test]
+    fn roles_of_works() {
+        let mut access_control = AccessControlData::<1, 4>::new();
+        let account = AccountId::from([1u8; 32]);
+
+        assert!(access_control.roles_of(account).is_empty());
+
+        access_control.set_account_roles::<()>(account, &[25, 3, 0, 9]);
+
+        assert_eq!(access_control.roles_of(account), [0, 3, 9, 25]);
+        assert_eq!(access_control.role_mask_of(account), RoleMask::of(&[0, 3, 9, 25]));
+    }
+
+    #[ink::test]
+    fn has_any_and_all_roles_read_each_page_once() {
+        let mut access_control = AccessControlData::<4>::new();
+        let account = AccountId::from([1u8; 32]);
+        let callee = ink::env::test::callee::<DefaultEnvironment>();
+        let reads = || ink::env::test::get_contract_storage_rw::<DefaultEnvironment>(&callee).0;
+
+        access_control.set_role::<()>(account, 1);
+        access_control.set_role::<()>(account, 2);
+
+        let before = reads();
+        assert!(access_control.has_all_roles(account, &[1, 2]));
+        assert_eq!(reads() - before, 1);
+
+        let before = reads();
+        assert!(access_control.has_any_role(account, &[3, 4, 2]));
+        assert_eq!(reads() - before, 1);
+    }
+
+    #[ink::test]
+    fn role_changes_emit_events() {
+        let mut access_control = AccessControlData::<4>::new();
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let (role, admin_role) = (1, 2);
+
+        // the sender of set_role and unset_role is the contract caller
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.alice);
+        access_control.set_role::<Recorder>(accounts.alice, DEFAULT_ADMIN_ROLE);
+        access_control.set_role::<Recorder>(accounts.alice, DEFAULT_ADMIN_ROLE);
+
+        assert_eq!(
+            Recorder::take(),
+            [Emitted::Granted(RoleGranted {
+                role:    DEFAULT_ADMIN_ROLE as RoleId,
+                account: accounts.alice,
+                sender:  accounts.alice,
+            })]
+        );
+
+        // the sender of grant_role and revoke_role is the one passed
+        // in, and nothing is emitted if nothing changed
+        access_control.grant_role::<Recorder>(accounts.alice, accounts.bob, role).unwrap();
+        access_control.revoke_role::<Recorder>(accounts.alice, accounts.bob, role).unwrap();
+        access_control.revoke_role::<Recorder>(accounts.alice, accounts.bob, role).unwrap();
+        access_control.unset_role::<Recorder>(accounts.charlie, role);
+
+        assert_eq!(
+            Recorder::take(),
+            [
+                Emitted::Granted(RoleGranted {
+                    role:    role as RoleId,
+                    account: accounts.bob,
+                    sender:  accounts.alice,
+                }),
+                Emitted::Revoked(RoleRevoked {
+                    role:    role as RoleId,
+                    account: accounts.bob,
+                    sender:  accounts.alice,
+                }),
+            ]
+        );
+
+        access_control.set_role_admin::<Recorder>(role, admin_role);
+
+        assert_eq!(
+            Recorder::take(),
+            [Emitted::AdminChanged(RoleAdminChanged {
+                role:                role as RoleId,
+                previous_admin_role: DEFAULT_ADMIN_ROLE as RoleId,
+                new_admin_role:      admin_role as RoleId,
+            })]
+        );
+    }
+
+    #[ink::test]
+    fn ensure_any_and_all_roles_work() {
+        let mut access_control = AccessControlData::<4>::new();
+        let account = AccountId::from([1u8; 32]);
+        let (r1, r2, r3) = (1, 2, 3);
+
+        access_control.set_role::<()>(account, r2);
+
+        assert_eq!(access_control.ensure_any_role(account, &[r1, r2]), Ok(()));
+        assert_eq!(
+            access_control.ensure_any_role(account, &[r1, r3]),
+            Err(AccessControlError::MissingRole {
+                account,
+                role: r1 as RoleId,
+            })
+        );
+        assert_eq!(
+            access_control.ensure_any_role(account, &[0usize; 0]),
+            Err(AccessControlError::EmptyRoles)
+        );
+
+        access_control.set_role::<()>(account, r3);
+
+        assert_eq!(access_control.ensure_all_roles(account, &[r2, r3]), Ok(()));
+        assert_eq!(
+            access_control.ensure_all_roles(account, &[r2, r1, r3]),
+            Err(AccessControlError::MissingRole {
+                account,
+                role: r1 as RoleId,
+            })
+        );
     }
 }
@@ -1,16 +1,183 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
-#![feature(inline_const)]
 
+// allows the macros, which refer to `::access_control`, to be used
+// inside this crate
+extern crate self as access_control;
+
+mod acceptance;
+mod admin_rules;
+mod batch;
+mod enumerable;
+mod error;
+mod events;
+mod expiry;
+mod freeze;
+mod hashed;
 mod internal;
-pub use internal::AccessControlData;
+mod mask;
+mod ownable;
+mod pausable;
+mod revocation;
+mod role;
+mod schedule;
+mod suspension;
+pub use access_control_macros::{
+    access_control, only_owner, only_role, when_not_paused, when_paused, Role,
+};
+pub use admin_rules::DefaultAdminRules;
+pub use error::AccessControlError;
+pub use events::{
+    AccessControlEvents, AccountFrozen, AccountUnfrozen, DefaultAdminDelayChangeCancelled,
+    DefaultAdminDelayChangeScheduled, DefaultAdminTransferCancelled, DefaultAdminTransferScheduled,
+    OwnableEvents, OwnershipTransferStarted, OwnershipTransferred, PausableEvents, Paused,
+    RoleAdminChanged, RoleGrantCancelled, RoleGrantExecuted, RoleGrantScheduled, RoleGranted,
+    RoleGrantedUntil, RoleOfferWithdrawn, RoleOffered, RoleResumed, RoleRevoked,
+    RoleRevokedFromAll, RoleSuspended, Unpaused,
+};
+pub use expiry::Expiry;
+pub use hashed::{role_hash, role_id, HashedAccessControlData, DEFAULT_ADMIN_ROLE_ID};
+pub use internal::{AccessControlData, BitMap, DEFAULT_ADMIN_ROLE};
+pub use mask::RoleMask;
+pub use ownable::OwnableData;
+pub use pausable::{HasRoles, PausableData};
+pub use role::Role;
+
+use ink::{prelude::vec::Vec, primitives::AccountId};
 
-use ink::primitives::AccountId;
+/// RoleId is the representation of a role in contract messages.
+/// `AccessControlData` works with `usize` bit positions, which can't
+/// be SCALE encoded, so they're converted to and from u32 at the
+/// contract boundary.
+pub type RoleId = u32;
 
+/// AccessControl is the role management interface exposed by
+/// contracts that embed `AccessControlData`. Implementing it gives
+/// every contract the same selectors for granting, revoking and
+/// querying roles.
 #[ink::trait_definition]
 pub trait AccessControl {
+    /// Grants `role` to `account`. The caller must hold the admin role
+    /// of `role`.
+    #[ink(message)]
+    fn grant_role(&mut self, role: RoleId, account: AccountId) -> Result<(), AccessControlError>;
+
+    /// Revokes `role` from `account`. The caller must hold the admin
+    /// role of `role`.
+    #[ink(message)]
+    fn revoke_role(&mut self, role: RoleId, account: AccountId) -> Result<(), AccessControlError>;
+
+    /// Revokes `role` from the caller. `account` must be the caller's
+    /// account, as a confirmation that it's not being renounced by
+    /// mistake.
+    #[ink(message)]
+    fn renounce_role(&mut self, role: RoleId, account: AccountId)
+        -> Result<(), AccessControlError>;
+
+    /// Returns true if `account` holds `role`.
+    #[ink(message)]
+    fn has_role(&self, role: RoleId, account: AccountId) -> bool;
+
+    /// Returns the role that administers `role`.
+    #[ink(message)]
+    fn get_role_admin(&self, role: RoleId) -> RoleId;
+}
+
+/// AccessControlBatch is the interface for changing many roles in a
+/// single call, exposed by contracts that embed `AccessControlData`.
+/// Each message either applies all of its changes or none of them.
+#[ink::trait_definition]
+pub trait AccessControlBatch {
+    /// Grants each role to its account. The caller must hold the admin
+    /// role of all of them.
+    #[ink(message)]
+    fn grant_roles(&mut self, roles: Vec<(RoleId, AccountId)>) -> Result<(), AccessControlError>;
+
+    /// Revokes each role from its account. The caller must hold the
+    /// admin role of all of them.
+    #[ink(message)]
+    fn revoke_roles(&mut self, roles: Vec<(RoleId, AccountId)>)
+        -> Result<(), AccessControlError>;
+
+    /// Replaces the roles of `account` with `roles`. The caller must
+    /// hold the admin role of every role granted or revoked.
+    #[ink(message)]
+    fn replace_roles(
+        &mut self,
+        account: AccountId,
+        roles: Vec<RoleId>,
+    ) -> Result<(), AccessControlError>;
+}
+
+/// AccessControlIntrospection is the interface for listing the roles
+/// of an account, exposed by contracts that embed `AccessControlData`.
+#[ink::trait_definition]
+pub trait AccessControlIntrospection {
+    /// Returns every role `account` holds, in ascending order.
+    #[ink(message)]
+    fn roles_of(&self, account: AccountId) -> Vec<RoleId>;
+}
+
+/// AccessControlEnumerable is the interface exposed by contracts whose
+/// `AccessControlData` was created with `new_enumerable`, which allows
+/// listing the accounts holding each role.
+#[ink::trait_definition]
+pub trait AccessControlEnumerable {
+    /// Returns the number of accounts holding `role`.
+    #[ink(message)]
+    fn get_role_member_count(&self, role: RoleId) -> u32;
+
+    /// Returns the account at `index` of the members of `role`.
+    #[ink(message)]
+    fn get_role_member(&self, role: RoleId, index: u32) -> Option<AccountId>;
+
+    /// Returns up to `limit` members of `role` starting at `offset`.
+    #[ink(message)]
+    fn role_members(&self, role: RoleId, offset: u32, limit: u32) -> Vec<AccountId>;
+}
+
+/// Ownable is the interface exposed by contracts that embed
+/// `OwnableData`, for querying and transferring their ownership.
+#[ink::trait_definition]
+pub trait Ownable {
+    /// Returns the owner of the contract, or None if the ownership was
+    /// renounced.
+    #[ink(message)]
+    fn owner(&self) -> Option<AccountId>;
+
+    /// Returns the account the ownership is being transferred to, if
+    /// any.
+    #[ink(message)]
+    fn pending_owner(&self) -> Option<AccountId>;
+
+    /// Starts transferring the ownership to `new_owner`, which has to
+    /// accept it. The caller must be the owner.
+    #[ink(message)]
+    fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), AccessControlError>;
+
+    /// Makes the caller the owner, if the ownership is being
+    /// transferred to it.
+    #[ink(message)]
+    fn accept_ownership(&mut self) -> Result<(), AccessControlError>;
+
+    /// Leaves the contract without owner. The caller must be the
+    /// owner.
+    #[ink(message)]
+    fn renounce_ownership(&mut self) -> Result<(), AccessControlError>;
+}
+
+/// Pausable is the interface exposed by contracts that embed
+/// `PausableData`, for pausing and unpausing them.
+#[ink::trait_definition]
+pub trait Pausable {
+    /// Returns true if the contract is paused.
+    #[ink(message)]
+    fn paused(&self) -> bool;
+
+    /// Pauses the contract. The caller must hold the pauser role.
     #[ink(message)]
-    fn grant_role(&mut self, account_id: AccountId);
+    fn pause(&mut self) -> Result<(), AccessControlError>;
 
+    /// Unpauses the contract. The caller must hold the pauser role.
     #[ink(message)]
-    fn revoke_role(&mut self, account_id: AccountId);
+    fn unpause(&mut self) -> Result<(), AccessControlError>;
 }
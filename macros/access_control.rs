@@ -0,0 +1,1166 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Expr, Fields, Ident, ImplItem, Item, ItemMod, ItemStruct, LitStr, Token, Type,
+};
+
+/// Layout is the kind of access control data stored in the contract
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Layout {
+    /// `AccessControlData`, where roles are bit positions
+    BitMap,
+    /// `HashedAccessControlData`, where roles are already `RoleId`s
+    Hashed,
+}
+
+impl Layout {
+    fn of(ty: &Type) -> Option<Self> {
+        let Type::Path(ty) = ty else {
+            return None;
+        };
+
+        match ty.path.segments.last()?.ident.to_string().as_str() {
+            "AccessControlData" => Some(Layout::BitMap),
+            "HashedAccessControlData" => Some(Layout::Hashed),
+            _ => None,
+        }
+    }
+}
+
+/// Args are the arguments of an `access_control` attribute
+pub struct Args {
+    pub field:      Option<Ident>,
+    pub enumerable: bool,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Args {
+            field:      None,
+            enumerable: false,
+        };
+
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+
+            if key == "field" {
+                input.parse::<Token![=]>()?;
+                args.field = Some(input.parse()?);
+            } else if key == "enumerable" {
+                args.enumerable = true;
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "expected `field = ...` or `enumerable`",
+                ));
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+pub fn expand(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let args: Args = syn::parse2(attr)?;
+    let mut module: ItemMod = syn::parse2(item)?;
+
+    let Some((_, items)) = module.content.as_mut() else {
+        return Err(syn::Error::new_spanned(
+            &module,
+            "access_control can only be used on inline contract modules",
+        ));
+    };
+
+    let storage = find_storage(items).ok_or_else(|| {
+        syn::Error::new(
+            Span::call_site(),
+            "access_control couldn't find the #[ink(storage)] struct of the contract",
+        )
+    })?;
+    let contract = storage.ident.clone();
+    let ownable = find_field(storage, "OwnableData")?;
+    let pausable = find_field(storage, "PausableData")?;
+    let access_control = match ownable {
+        // contracts that only have an owner don't need any roles
+        Some(_) if args.field.is_none() && !has_access_control_field(storage) => None,
+        _ => Some(find_access_control_field(storage, args.field.as_ref())?),
+    };
+
+    if let Some((field, layout)) = &access_control {
+        let (field, layout) = (field, *layout);
+        if args.enumerable && layout == Layout::Hashed {
+            return Err(syn::Error::new(
+                field.span(),
+                "HashedAccessControlData can't be enumerable",
+            ));
+        }
+
+        if layout == Layout::Hashed {
+            let checks = generate_role_id_checks(items);
+            items.extend(checks);
+        }
+
+        items.extend(generate(&contract, field, layout, args.enumerable));
+    } else if args.enumerable {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "enumerable requires an AccessControlData field",
+        ));
+    }
+
+    if let Some(field) = ownable {
+        items.extend(generate_ownable(&contract, &field));
+    }
+
+    if let Some(field) = pausable {
+        let Some((roles, _)) = &access_control else {
+            return Err(syn::Error::new(
+                field.span(),
+                "PausableData requires an AccessControlData or HashedAccessControlData field",
+            ));
+        };
+
+        items.extend(generate_pausable(&contract, &field, roles));
+    }
+
+    Ok(quote!(#module))
+}
+
+fn find_storage(items: &[Item]) -> Option<&ItemStruct> {
+    items.iter().find_map(|item| match item {
+        Item::Struct(item) if item.attrs.iter().any(is_ink_storage) => Some(item),
+        _ => None,
+    })
+}
+
+fn is_ink_storage(attr: &syn::Attribute) -> bool {
+    attr.path().is_ident("ink")
+        && attr
+            .parse_args::<Ident>()
+            .is_ok_and(|arg| arg == "storage")
+}
+
+fn has_access_control_field(storage: &ItemStruct) -> bool {
+    storage
+        .fields
+        .iter()
+        .any(|field| Layout::of(&field.ty).is_some())
+}
+
+/// find_field returns the field of the storage struct whose type is
+/// named `ty`, like `OwnableData`, if there's one
+fn find_field(storage: &ItemStruct, ty: &str) -> syn::Result<Option<Ident>> {
+    let mut candidates = storage.fields.iter().filter(|field| {
+        matches!(&field.ty, Type::Path(path)
+            if path.path.segments.last().is_some_and(|segment| segment.ident == ty))
+    });
+
+    match (candidates.next(), candidates.next()) {
+        (_, Some(other)) => Err(syn::Error::new_spanned(
+            other,
+            format!("found more than one {ty} field"),
+        )),
+        (field, None) => Ok(field.and_then(|field| field.ident.clone())),
+    }
+}
+
+/// find_access_control_field returns the field of the storage struct
+/// named `name`, or the only one whose type is `AccessControlData` or
+/// `HashedAccessControlData` if it's not given
+fn find_access_control_field(
+    storage: &ItemStruct,
+    name: Option<&Ident>,
+) -> syn::Result<(Ident, Layout)> {
+    let Fields::Named(fields) = &storage.fields else {
+        return Err(syn::Error::new_spanned(
+            &storage.fields,
+            "access_control requires a storage struct with named fields",
+        ));
+    };
+
+    let mut candidates = fields.named.iter().filter(|field| match name {
+        Some(name) => field.ident.as_ref() == Some(name),
+        None => Layout::of(&field.ty).is_some(),
+    });
+
+    match (candidates.next(), candidates.next()) {
+        (Some(field), None) => {
+            let layout = Layout::of(&field.ty).ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &field.ty,
+                    "expected an AccessControlData or HashedAccessControlData field",
+                )
+            })?;
+
+            Ok((field.ident.clone().expect("fields are named"), layout))
+        }
+        (None, _) => Err(syn::Error::new_spanned(
+            &storage.ident,
+            "access_control couldn't find an AccessControlData or OwnableData field",
+        )),
+        (Some(_), Some(other)) => Err(syn::Error::new_spanned(
+            other,
+            "found more than one AccessControlData field, pick one with `field = ...`",
+        )),
+    }
+}
+
+fn generate(contract: &Ident, field: &Ident, layout: Layout, enumerable: bool) -> Vec<Item> {
+    let env = quote!(<Self as ::ink::codegen::StaticEnv>::env());
+    let caller = quote!(#env.caller());
+    // the contract has to be explicit, otherwise emit_event is ambiguous
+    // when there's more than one contract in the crate
+    let emit = quote!(::ink::codegen::EmitEvent::<#contract>::emit_event);
+    // AccessControlData works with bit positions, so the RoleIds of the
+    // messages have to be converted to and from them
+    let (to_role, from_role) = match layout {
+        Layout::BitMap => (quote!(as usize), quote!(as ::access_control::RoleId)),
+        Layout::Hashed => (quote!(), quote!()),
+    };
+    // only AccessControlData can schedule grants, offer roles, enforce
+    // the default admin rules and suspend roles
+    let extra_emits = match layout {
+        Layout::BitMap => quote! {
+            fn emit_role_granted_until(event: ::access_control::RoleGrantedUntil) {
+                #emit(#env, RoleGrantedUntil {
+                    role:    event.role,
+                    account: event.account,
+                    sender:  event.sender,
+                    expiry:  event.expiry,
+                });
+            }
+
+            fn emit_role_grant_scheduled(event: ::access_control::RoleGrantScheduled) {
+                #emit(#env, RoleGrantScheduled {
+                    role:     event.role,
+                    account:  event.account,
+                    sender:   event.sender,
+                    ready_at: event.ready_at,
+                });
+            }
+
+            fn emit_role_grant_executed(event: ::access_control::RoleGrantExecuted) {
+                #emit(#env, RoleGrantExecuted {
+                    role:    event.role,
+                    account: event.account,
+                    sender:  event.sender,
+                });
+            }
+
+            fn emit_role_grant_cancelled(event: ::access_control::RoleGrantCancelled) {
+                #emit(#env, RoleGrantCancelled {
+                    role:    event.role,
+                    account: event.account,
+                    sender:  event.sender,
+                });
+            }
+
+            fn emit_role_offered(event: ::access_control::RoleOffered) {
+                #emit(#env, RoleOffered {
+                    role:    event.role,
+                    account: event.account,
+                    sender:  event.sender,
+                });
+            }
+
+            fn emit_role_offer_withdrawn(event: ::access_control::RoleOfferWithdrawn) {
+                #emit(#env, RoleOfferWithdrawn {
+                    role:    event.role,
+                    account: event.account,
+                    sender:  event.sender,
+                });
+            }
+
+            fn emit_default_admin_transfer_scheduled(
+                event: ::access_control::DefaultAdminTransferScheduled,
+            ) {
+                #emit(#env, DefaultAdminTransferScheduled {
+                    new_admin: event.new_admin,
+                    ready_at:  event.ready_at,
+                });
+            }
+
+            fn emit_default_admin_transfer_cancelled(
+                event: ::access_control::DefaultAdminTransferCancelled,
+            ) {
+                #emit(#env, DefaultAdminTransferCancelled {
+                    new_admin: event.new_admin,
+                });
+            }
+
+            fn emit_default_admin_delay_change_scheduled(
+                event: ::access_control::DefaultAdminDelayChangeScheduled,
+            ) {
+                #emit(#env, DefaultAdminDelayChangeScheduled {
+                    new_delay: event.new_delay,
+                    effect_at: event.effect_at,
+                });
+            }
+
+            fn emit_default_admin_delay_change_cancelled(
+                event: ::access_control::DefaultAdminDelayChangeCancelled,
+            ) {
+                #emit(#env, DefaultAdminDelayChangeCancelled {
+                    new_delay: event.new_delay,
+                });
+            }
+
+            fn emit_role_suspended(event: ::access_control::RoleSuspended) {
+                #emit(#env, RoleSuspended {
+                    role:   event.role,
+                    sender: event.sender,
+                });
+            }
+
+            fn emit_role_resumed(event: ::access_control::RoleResumed) {
+                #emit(#env, RoleResumed {
+                    role:   event.role,
+                    sender: event.sender,
+                });
+            }
+
+            fn emit_account_frozen(event: ::access_control::AccountFrozen) {
+                #emit(#env, AccountFrozen {
+                    account: event.account,
+                    sender:  event.sender,
+                    until:   event.until,
+                });
+            }
+
+            fn emit_account_unfrozen(event: ::access_control::AccountUnfrozen) {
+                #emit(#env, AccountUnfrozen {
+                    account: event.account,
+                    sender:  event.sender,
+                });
+            }
+
+            fn emit_role_revoked_from_all(event: ::access_control::RoleRevokedFromAll) {
+                #emit(#env, RoleRevokedFromAll {
+                    role:       event.role,
+                    generation: event.generation,
+                    sender:     event.sender,
+                });
+            }
+        },
+        Layout::Hashed => quote!(),
+    };
+
+    let mut items: Vec<Item> = vec![
+        syn::parse_quote! {
+            #[ink(event)]
+            pub struct RoleGranted {
+                #[ink(topic)]
+                role:    ::access_control::RoleId,
+                #[ink(topic)]
+                account: ::ink::primitives::AccountId,
+                sender:  ::ink::primitives::AccountId,
+            }
+        },
+        syn::parse_quote! {
+            #[ink(event)]
+            pub struct RoleRevoked {
+                #[ink(topic)]
+                role:    ::access_control::RoleId,
+                #[ink(topic)]
+                account: ::ink::primitives::AccountId,
+                sender:  ::ink::primitives::AccountId,
+            }
+        },
+        syn::parse_quote! {
+            #[ink(event)]
+            pub struct RoleAdminChanged {
+                #[ink(topic)]
+                role:                ::access_control::RoleId,
+                previous_admin_role: ::access_control::RoleId,
+                new_admin_role:      ::access_control::RoleId,
+            }
+        },
+        syn::parse_quote! {
+            impl ::access_control::AccessControlEvents for #contract {
+                fn emit_role_granted(event: ::access_control::RoleGranted) {
+                    #emit(#env, RoleGranted {
+                        role:    event.role,
+                        account: event.account,
+                        sender:  event.sender,
+                    });
+                }
+
+                fn emit_role_revoked(event: ::access_control::RoleRevoked) {
+                    #emit(#env, RoleRevoked {
+                        role:    event.role,
+                        account: event.account,
+                        sender:  event.sender,
+                    });
+                }
+
+                fn emit_role_admin_changed(event: ::access_control::RoleAdminChanged) {
+                    #emit(#env, RoleAdminChanged {
+                        role:                event.role,
+                        previous_admin_role: event.previous_admin_role,
+                        new_admin_role:      event.new_admin_role,
+                    });
+                }
+
+                #extra_emits
+            }
+        },
+        syn::parse_quote! {
+            impl ::access_control::AccessControl for #contract {
+                #[ink(message)]
+                fn grant_role(
+                    &mut self,
+                    role: ::access_control::RoleId,
+                    account: ::ink::primitives::AccountId,
+                ) -> ::core::result::Result<(), ::access_control::AccessControlError> {
+                    self.#field.grant_role::<Self>(#caller, account, role #to_role)
+                }
+
+                #[ink(message)]
+                fn revoke_role(
+                    &mut self,
+                    role: ::access_control::RoleId,
+                    account: ::ink::primitives::AccountId,
+                ) -> ::core::result::Result<(), ::access_control::AccessControlError> {
+                    self.#field.revoke_role::<Self>(#caller, account, role #to_role)
+                }
+
+                #[ink(message)]
+                fn renounce_role(
+                    &mut self,
+                    role: ::access_control::RoleId,
+                    account: ::ink::primitives::AccountId,
+                ) -> ::core::result::Result<(), ::access_control::AccessControlError> {
+                    self.#field.renounce_role::<Self>(#caller, account, role #to_role)
+                }
+
+                #[ink(message)]
+                fn has_role(
+                    &self,
+                    role: ::access_control::RoleId,
+                    account: ::ink::primitives::AccountId,
+                ) -> bool {
+                    self.#field.has_role(account, role #to_role)
+                }
+
+                #[ink(message)]
+                fn get_role_admin(
+                    &self,
+                    role: ::access_control::RoleId,
+                ) -> ::access_control::RoleId {
+                    self.#field.get_role_admin(role #to_role) #from_role
+                }
+            }
+        },
+    ];
+
+    if layout == Layout::BitMap {
+        items.push(syn::parse_quote! {
+            #[ink(event)]
+            pub struct RoleGrantedUntil {
+                #[ink(topic)]
+                role:    ::access_control::RoleId,
+                #[ink(topic)]
+                account: ::ink::primitives::AccountId,
+                sender:  ::ink::primitives::AccountId,
+                expiry:  ::access_control::Expiry,
+            }
+        });
+        items.push(syn::parse_quote! {
+            #[ink(event)]
+            pub struct RoleGrantScheduled {
+                #[ink(topic)]
+                role:     ::access_control::RoleId,
+                #[ink(topic)]
+                account:  ::ink::primitives::AccountId,
+                sender:   ::ink::primitives::AccountId,
+                ready_at: u64,
+            }
+        });
+        items.push(syn::parse_quote! {
+            #[ink(event)]
+            pub struct RoleGrantExecuted {
+                #[ink(topic)]
+                role:    ::access_control::RoleId,
+                #[ink(topic)]
+                account: ::ink::primitives::AccountId,
+                sender:  ::ink::primitives::AccountId,
+            }
+        });
+        items.push(syn::parse_quote! {
+            #[ink(event)]
+            pub struct RoleGrantCancelled {
+                #[ink(topic)]
+                role:    ::access_control::RoleId,
+                #[ink(topic)]
+                account: ::ink::primitives::AccountId,
+                sender:  ::ink::primitives::AccountId,
+            }
+        });
+        items.push(syn::parse_quote! {
+            #[ink(event)]
+            pub struct RoleOffered {
+                #[ink(topic)]
+                role:    ::access_control::RoleId,
+                #[ink(topic)]
+                account: ::ink::primitives::AccountId,
+                sender:  ::ink::primitives::AccountId,
+            }
+        });
+        items.push(syn::parse_quote! {
+            #[ink(event)]
+            pub struct RoleOfferWithdrawn {
+                #[ink(topic)]
+                role:    ::access_control::RoleId,
+                #[ink(topic)]
+                account: ::ink::primitives::AccountId,
+                sender:  ::ink::primitives::AccountId,
+            }
+        });
+        items.push(syn::parse_quote! {
+            #[ink(event)]
+            pub struct DefaultAdminTransferScheduled {
+                #[ink(topic)]
+                new_admin: ::ink::primitives::AccountId,
+                ready_at:  u64,
+            }
+        });
+        items.push(syn::parse_quote! {
+            #[ink(event)]
+            pub struct DefaultAdminTransferCancelled {
+                #[ink(topic)]
+                new_admin: ::ink::primitives::AccountId,
+            }
+        });
+        items.push(syn::parse_quote! {
+            #[ink(event)]
+            pub struct DefaultAdminDelayChangeScheduled {
+                new_delay: u64,
+                effect_at: u64,
+            }
+        });
+        items.push(syn::parse_quote! {
+            #[ink(event)]
+            pub struct DefaultAdminDelayChangeCancelled {
+                new_delay: u64,
+            }
+        });
+        items.push(syn::parse_quote! {
+            #[ink(event)]
+            pub struct RoleSuspended {
+                #[ink(topic)]
+                role:   ::access_control::RoleId,
+                sender: ::ink::primitives::AccountId,
+            }
+        });
+        items.push(syn::parse_quote! {
+            #[ink(event)]
+            pub struct RoleResumed {
+                #[ink(topic)]
+                role:   ::access_control::RoleId,
+                sender: ::ink::primitives::AccountId,
+            }
+        });
+        items.push(syn::parse_quote! {
+            #[ink(event)]
+            pub struct AccountFrozen {
+                #[ink(topic)]
+                account: ::ink::primitives::AccountId,
+                sender:  ::ink::primitives::AccountId,
+                until:   ::core::option::Option<u64>,
+            }
+        });
+        items.push(syn::parse_quote! {
+            #[ink(event)]
+            pub struct AccountUnfrozen {
+                #[ink(topic)]
+                account: ::ink::primitives::AccountId,
+                sender:  ::ink::primitives::AccountId,
+            }
+        });
+        items.push(syn::parse_quote! {
+            #[ink(event)]
+            pub struct RoleRevokedFromAll {
+                #[ink(topic)]
+                role:       ::access_control::RoleId,
+                generation: u32,
+                sender:     ::ink::primitives::AccountId,
+            }
+        });
+        items.push(syn::parse_quote! {
+            impl ::access_control::AccessControlBatch for #contract {
+                #[ink(message)]
+                fn grant_roles(
+                    &mut self,
+                    roles: ::ink::prelude::vec::Vec<(
+                        ::access_control::RoleId,
+                        ::ink::primitives::AccountId,
+                    )>,
+                ) -> ::core::result::Result<(), ::access_control::AccessControlError> {
+                    let roles: ::ink::prelude::vec::Vec<_> = roles
+                        .into_iter()
+                        .map(|(role, account)| (account, role as usize))
+                        .collect();
+                    self.#field.grant_roles::<Self>(#caller, &roles)
+                }
+
+                #[ink(message)]
+                fn revoke_roles(
+                    &mut self,
+                    roles: ::ink::prelude::vec::Vec<(
+                        ::access_control::RoleId,
+                        ::ink::primitives::AccountId,
+                    )>,
+                ) -> ::core::result::Result<(), ::access_control::AccessControlError> {
+                    let roles: ::ink::prelude::vec::Vec<_> = roles
+                        .into_iter()
+                        .map(|(role, account)| (account, role as usize))
+                        .collect();
+                    self.#field.revoke_roles::<Self>(#caller, &roles)
+                }
+
+                #[ink(message)]
+                fn replace_roles(
+                    &mut self,
+                    account: ::ink::primitives::AccountId,
+                    roles: ::ink::prelude::vec::Vec<::access_control::RoleId>,
+                ) -> ::core::result::Result<(), ::access_control::AccessControlError> {
+                    let roles: ::ink::prelude::vec::Vec<usize> =
+                        roles.into_iter().map(|role| role as usize).collect();
+                    let mask = ::access_control::RoleMask::try_from_roles(&roles)?;
+                    self.#field.replace_roles::<Self>(#caller, account, &mask)
+                }
+            }
+        });
+    }
+
+    if layout == Layout::BitMap {
+        items.push(syn::parse_quote! {
+            impl ::access_control::AccessControlIntrospection for #contract {
+                #[ink(message)]
+                fn roles_of(
+                    &self,
+                    account: ::ink::primitives::AccountId,
+                ) -> ::ink::prelude::vec::Vec<::access_control::RoleId> {
+                    self.#field
+                        .roles_of(account)
+                        .into_iter()
+                        .map(|role| role as ::access_control::RoleId)
+                        .collect()
+                }
+            }
+        });
+    }
+
+    if enumerable {
+        items.push(syn::parse_quote! {
+            impl ::access_control::AccessControlEnumerable for #contract {
+                #[ink(message)]
+                fn get_role_member_count(&self, role: ::access_control::RoleId) -> u32 {
+                    self.#field.get_role_member_count(role as usize)
+                }
+
+                #[ink(message)]
+                fn get_role_member(
+                    &self,
+                    role: ::access_control::RoleId,
+                    index: u32,
+                ) -> ::core::option::Option<::ink::primitives::AccountId> {
+                    self.#field.get_role_member(role as usize, index)
+                }
+
+                #[ink(message)]
+                fn role_members(
+                    &self,
+                    role: ::access_control::RoleId,
+                    offset: u32,
+                    limit: u32,
+                ) -> ::ink::prelude::vec::Vec<::ink::primitives::AccountId> {
+                    self.#field.role_members(role as usize, offset, limit)
+                }
+            }
+        });
+    }
+
+    items
+}
+
+fn generate_ownable(contract: &Ident, field: &Ident) -> Vec<Item> {
+    let env = quote!(<Self as ::ink::codegen::StaticEnv>::env());
+    let caller = quote!(#env.caller());
+    let emit = quote!(::ink::codegen::EmitEvent::<#contract>::emit_event);
+
+    vec![
+        syn::parse_quote! {
+            #[ink(event)]
+            pub struct OwnershipTransferStarted {
+                #[ink(topic)]
+                previous_owner: ::ink::primitives::AccountId,
+                #[ink(topic)]
+                new_owner:      ::ink::primitives::AccountId,
+            }
+        },
+        syn::parse_quote! {
+            #[ink(event)]
+            pub struct OwnershipTransferred {
+                #[ink(topic)]
+                previous_owner: ::core::option::Option<::ink::primitives::AccountId>,
+                #[ink(topic)]
+                new_owner:      ::core::option::Option<::ink::primitives::AccountId>,
+            }
+        },
+        syn::parse_quote! {
+            impl ::access_control::OwnableEvents for #contract {
+                fn emit_ownership_transfer_started(
+                    event: ::access_control::OwnershipTransferStarted,
+                ) {
+                    #emit(#env, OwnershipTransferStarted {
+                        previous_owner: event.previous_owner,
+                        new_owner:      event.new_owner,
+                    });
+                }
+
+                fn emit_ownership_transferred(event: ::access_control::OwnershipTransferred) {
+                    #emit(#env, OwnershipTransferred {
+                        previous_owner: event.previous_owner,
+                        new_owner:      event.new_owner,
+                    });
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl ::access_control::Ownable for #contract {
+                #[ink(message)]
+                fn owner(&self) -> ::core::option::Option<::ink::primitives::AccountId> {
+                    self.#field.owner()
+                }
+
+                #[ink(message)]
+                fn pending_owner(&self) -> ::core::option::Option<::ink::primitives::AccountId> {
+                    self.#field.pending_owner()
+                }
+
+                #[ink(message)]
+                fn transfer_ownership(
+                    &mut self,
+                    new_owner: ::ink::primitives::AccountId,
+                ) -> ::core::result::Result<(), ::access_control::AccessControlError> {
+                    self.#field.transfer_ownership::<Self>(#caller, new_owner)
+                }
+
+                #[ink(message)]
+                fn accept_ownership(
+                    &mut self,
+                ) -> ::core::result::Result<(), ::access_control::AccessControlError> {
+                    self.#field.accept_ownership::<Self>(#caller)
+                }
+
+                #[ink(message)]
+                fn renounce_ownership(
+                    &mut self,
+                ) -> ::core::result::Result<(), ::access_control::AccessControlError> {
+                    self.#field.renounce_ownership::<Self>(#caller)
+                }
+            }
+        },
+    ]
+}
+
+fn generate_pausable(contract: &Ident, field: &Ident, roles: &Ident) -> Vec<Item> {
+    let env = quote!(<Self as ::ink::codegen::StaticEnv>::env());
+    let caller = quote!(#env.caller());
+    let emit = quote!(::ink::codegen::EmitEvent::<#contract>::emit_event);
+
+    vec![
+        syn::parse_quote! {
+            #[ink(event)]
+            pub struct Paused {
+                #[ink(topic)]
+                account: ::ink::primitives::AccountId,
+            }
+        },
+        syn::parse_quote! {
+            #[ink(event)]
+            pub struct Unpaused {
+                #[ink(topic)]
+                account: ::ink::primitives::AccountId,
+            }
+        },
+        syn::parse_quote! {
+            impl ::access_control::PausableEvents for #contract {
+                fn emit_paused(event: ::access_control::Paused) {
+                    #emit(#env, Paused {
+                        account: event.account,
+                    });
+                }
+
+                fn emit_unpaused(event: ::access_control::Unpaused) {
+                    #emit(#env, Unpaused {
+                        account: event.account,
+                    });
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl ::access_control::Pausable for #contract {
+                #[ink(message)]
+                fn paused(&self) -> bool {
+                    self.#field.is_paused()
+                }
+
+                #[ink(message)]
+                fn pause(
+                    &mut self,
+                ) -> ::core::result::Result<(), ::access_control::AccessControlError> {
+                    self.#field.pause::<Self>(&self.#roles, #caller)
+                }
+
+                #[ink(message)]
+                fn unpause(
+                    &mut self,
+                ) -> ::core::result::Result<(), ::access_control::AccessControlError> {
+                    self.#field.unpause::<Self>(&self.#roles, #caller)
+                }
+            }
+        },
+    ]
+}
+
+/// generate_role_id_checks returns a constant that fails to compile
+/// if two of the role ids of the contract are the same. The role ids
+/// are the constants of the module, or of the inherent impls in it,
+/// initialized with `role_id(...)`, along with `DEFAULT_ADMIN_ROLE_ID`.
+fn generate_role_id_checks(items: &[Item]) -> Option<Item> {
+    let mut roles = vec![(
+        "DEFAULT_ADMIN_ROLE_ID".to_string(),
+        quote!(::access_control::DEFAULT_ADMIN_ROLE_ID),
+    )];
+
+    for item in items {
+        match item {
+            Item::Const(item) if is_role_id_call(&item.expr) => {
+                let ident = &item.ident;
+                roles.push((ident.to_string(), quote!(#ident)));
+            }
+            Item::Impl(item) if item.trait_.is_none() && item.generics.params.is_empty() => {
+                let ty = &item.self_ty;
+
+                for item in &item.items {
+                    if let ImplItem::Const(item) = item {
+                        if is_role_id_call(&item.expr) {
+                            let ident = &item.ident;
+                            let name = format!("{}::{ident}", quote!(#ty));
+                            roles.push((name, quote!(<#ty>::#ident)));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if roles.len() < 2 {
+        return None;
+    }
+
+    let mut checks = Vec::new();
+    for (i, (name, role)) in roles.iter().enumerate() {
+        for (other_name, other) in &roles[i + 1..] {
+            let message = LitStr::new(
+                &format!("the roles {name} and {other_name} have the same id"),
+                Span::call_site(),
+            );
+            checks.push(quote!(::core::assert!(#role != #other, #message);));
+        }
+    }
+
+    Some(syn::parse_quote! {
+        const _: () = {
+            #(#checks)*
+        };
+    })
+}
+
+/// is_role_id_call returns true if `expr` is a call to `role_id`
+fn is_role_id_call(expr: &Expr) -> bool {
+    let Expr::Call(call) = expr else {
+        return false;
+    };
+
+    matches!(&*call.func, Expr::Path(func)
+        if func.path.segments.last().is_some_and(|segment| segment.ident == "role_id"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// generated returns the names of the events of `module` and of the
+    /// traits implemented in it
+    fn generated(module: ItemMod) -> (Vec<String>, Vec<String>) {
+        let (mut events, mut traits) = (Vec::new(), Vec::new());
+
+        for item in module.content.unwrap().1 {
+            match item {
+                Item::Struct(item) if item.attrs.iter().any(|attr| {
+                    attr.path().is_ident("ink")
+                        && attr.parse_args::<Ident>().is_ok_and(|arg| arg == "event")
+                }) =>
+                {
+                    events.push(item.ident.to_string())
+                }
+                Item::Impl(item) => {
+                    if let Some((_, path, _)) = item.trait_ {
+                        traits.push(path.segments.last().unwrap().ident.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        (events, traits)
+    }
+
+    #[test]
+    fn parses_args() {
+        let args: Args = syn::parse_quote!();
+        assert!(args.field.is_none());
+        assert!(!args.enumerable);
+
+        let args: Args = syn::parse_quote!(field = roles, enumerable);
+        assert_eq!(args.field.unwrap(), "roles");
+        assert!(args.enumerable);
+
+        assert!(syn::parse_str::<Args>("other").is_err());
+    }
+
+    #[test]
+    fn finds_access_control_field() {
+        let storage: ItemStruct = syn::parse_quote! {
+            #[ink(storage)]
+            pub struct Contract {
+                value: bool,
+                roles: access_control::AccessControlData<4>,
+            }
+        };
+
+        let (field, layout) = find_access_control_field(&storage, None).unwrap();
+        assert_eq!(field, "roles");
+        assert_eq!(layout, Layout::BitMap);
+    }
+
+    #[test]
+    fn finds_hashed_and_named_fields() {
+        let storage: ItemStruct = syn::parse_quote! {
+            #[ink(storage)]
+            pub struct Contract {
+                a: AccessControlData<4>,
+                b: HashedAccessControlData,
+            }
+        };
+
+        let name: Ident = syn::parse_quote!(b);
+        let (field, layout) = find_access_control_field(&storage, Some(&name)).unwrap();
+        assert_eq!(field, "b");
+        assert_eq!(layout, Layout::Hashed);
+
+        // the named field must still be access control data
+        let storage: ItemStruct = syn::parse_quote! {
+            pub struct Contract {
+                b: bool,
+            }
+        };
+        assert!(find_access_control_field(&storage, Some(&name)).is_err());
+    }
+
+    #[test]
+    fn rejects_ambiguous_or_missing_fields() {
+        let storage: ItemStruct = syn::parse_quote! {
+            pub struct Contract {
+                a: AccessControlData<4>,
+                b: AccessControlData<4>,
+            }
+        };
+        assert!(find_access_control_field(&storage, None).is_err());
+
+        let storage: ItemStruct = syn::parse_quote! {
+            pub struct Contract {
+                value: bool,
+            }
+        };
+        assert!(find_access_control_field(&storage, None).is_err());
+    }
+
+    #[test]
+    fn generates_into_the_contract_module() {
+        let res = expand(
+            quote!(),
+            quote! {
+                mod contract {
+                    #[ink(storage)]
+                    pub struct Contract {
+                        access_control: AccessControlData<4>,
+                    }
+                }
+            },
+        )
+        .unwrap();
+        let (events, traits) = generated(syn::parse2(res).unwrap());
+
+        for event in [
+            "RoleGranted",
+            "RoleRevoked",
+            "RoleAdminChanged",
+            "RoleGrantedUntil",
+            "RoleGrantScheduled",
+            "RoleOffered",
+            "DefaultAdminTransferScheduled",
+            "RoleSuspended",
+            "AccountFrozen",
+            "RoleRevokedFromAll",
+        ] {
+            assert!(events.iter().any(|name| name == event), "{event} isn't generated");
+        }
+        assert_eq!(
+            traits,
+            [
+                "AccessControlEvents",
+                "AccessControl",
+                "AccessControlBatch",
+                "AccessControlIntrospection"
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_that_role_ids_are_distinct() {
+        let res = expand(
+            quote!(),
+            quote! {
+                mod contract {
+                    const PAUSER: RoleId = role_id("PAUSER");
+
+                    #[ink(storage)]
+                    pub struct Contract {
+                        roles: HashedAccessControlData,
+                    }
+
+                    impl Contract {
+                        pub const MINTER: RoleId = role_id("MINTER");
+                        pub const LIMIT: u32 = 10;
+                    }
+                }
+            },
+        )
+        .unwrap()
+        .to_string();
+
+        for message in [
+            "the roles DEFAULT_ADMIN_ROLE_ID and PAUSER have the same id",
+            "the roles DEFAULT_ADMIN_ROLE_ID and Contract::MINTER have the same id",
+            "the roles PAUSER and Contract::MINTER have the same id",
+        ] {
+            assert!(res.contains(message), "{message} isn't checked");
+        }
+        assert!(!res.contains("LIMIT have"));
+    }
+
+    #[test]
+    fn generates_pausable() {
+        let res = expand(
+            quote!(),
+            quote! {
+                mod contract {
+                    #[ink(storage)]
+                    pub struct Contract {
+                        roles:    HashedAccessControlData,
+                        pausable: PausableData,
+                    }
+                }
+            },
+        )
+        .unwrap();
+        let (events, traits) = generated(syn::parse2(res).unwrap());
+
+        // hashed roles only get the events of AccessControl
+        assert_eq!(
+            events,
+            ["RoleGranted", "RoleRevoked", "RoleAdminChanged", "Paused", "Unpaused"]
+        );
+        assert_eq!(
+            traits,
+            ["AccessControlEvents", "AccessControl", "PausableEvents", "Pausable"]
+        );
+    }
+
+    #[test]
+    fn generates_ownable_with_or_without_roles() {
+        let res = expand(
+            quote!(),
+            quote! {
+                mod contract {
+                    #[ink(storage)]
+                    pub struct Contract {
+                        ownable: OwnableData,
+                    }
+                }
+            },
+        )
+        .unwrap();
+        let (events, traits) = generated(syn::parse2(res).unwrap());
+        assert_eq!(events, ["OwnershipTransferStarted", "OwnershipTransferred"]);
+        assert_eq!(traits, ["OwnableEvents", "Ownable"]);
+
+        let res = expand(
+            quote!(),
+            quote! {
+                mod contract {
+                    #[ink(storage)]
+                    pub struct Contract {
+                        ownable:        OwnableData,
+                        access_control: AccessControlData<4>,
+                    }
+                }
+            },
+        )
+        .unwrap();
+        let (events, traits) = generated(syn::parse2(res).unwrap());
+        assert!(events.iter().any(|name| name == "RoleGranted"));
+        assert!(events.iter().any(|name| name == "OwnershipTransferred"));
+        assert!(traits.iter().any(|name| name == "AccessControl"));
+        assert!(traits.iter().any(|name| name == "Ownable"));
+
+        // pausing needs roles to pause with
+        let res = expand(
+            quote!(),
+            quote! {
+                mod contract {
+                    #[ink(storage)]
+                    pub struct Contract {
+                        ownable:  OwnableData,
+                        pausable: PausableData,
+                    }
+                }
+            },
+        );
+        assert!(res.is_err());
+
+        // enumerable needs roles to enumerate
+        let res = expand(
+            quote!(enumerable),
+            quote! {
+                mod contract {
+                    #[ink(storage)]
+                    pub struct Contract {
+                        ownable: OwnableData,
+                    }
+                }
+            },
+        );
+        assert!(res.is_err());
+    }
+}
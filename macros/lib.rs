@@ -0,0 +1,167 @@
+//! Procedural macros for the access_control crate. They're re-exported
+//! from it, so contracts shouldn't depend on this crate directly.
+
+mod access_control;
+mod only_owner;
+mod only_role;
+mod role;
+mod when_paused;
+
+use proc_macro::TokenStream;
+
+/// Guards an ink! message so that it fails with
+/// `AccessControlError::MissingRole` unless the caller holds the given
+/// role. The message must return a `Result` whose error type
+/// implements `From<AccessControlError>`.
+///
+/// ```ignore
+/// #[ink(message)]
+/// #[only_role(Self::MINTER)]
+/// pub fn mint(&mut self) -> Result<(), AccessControlError> { ... }
+///
+/// #[ink(message)]
+/// #[only_role(any_of(Self::MINTER, Self::ADMIN))]
+/// pub fn burn(&mut self) -> Result<(), AccessControlError> { ... }
+///
+/// #[ink(message)]
+/// #[only_role(all_of(Self::KYC, Self::TRADER), field = roles)]
+/// pub fn trade(&mut self) -> Result<(), AccessControlError> { ... }
+/// ```
+///
+/// The check is done against the `access_control` field of the
+/// contract, `field = ...` can be used to pick a different one.
+#[proc_macro_attribute]
+pub fn only_role(attr: TokenStream, item: TokenStream) -> TokenStream {
+    only_role::expand(attr.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Guards an ink! message so that it fails with
+/// `AccessControlError::NotOwner` unless the caller is the owner of the
+/// contract. The message must return a `Result` whose error type
+/// implements `From<AccessControlError>`.
+///
+/// ```ignore
+/// #[ink(message)]
+/// #[only_owner]
+/// pub fn set_fee(&mut self, fee: u32) -> Result<(), AccessControlError> { ... }
+/// ```
+///
+/// The check is done against the `ownable` field of the contract,
+/// `field = ...` can be used to pick a different one.
+#[proc_macro_attribute]
+pub fn only_owner(attr: TokenStream, item: TokenStream) -> TokenStream {
+    only_owner::expand(attr.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Guards an ink! message so that it fails with
+/// `AccessControlError::EnforcedPause` while the contract is paused.
+/// The message must return a `Result` whose error type implements
+/// `From<AccessControlError>`.
+///
+/// ```ignore
+/// #[ink(message)]
+/// #[when_not_paused]
+/// pub fn transfer(&mut self, to: AccountId) -> Result<(), AccessControlError> { ... }
+/// ```
+///
+/// The check is done against the `pausable` field of the contract,
+/// `field = ...` can be used to pick a different one.
+#[proc_macro_attribute]
+pub fn when_not_paused(attr: TokenStream, item: TokenStream) -> TokenStream {
+    when_paused::expand(attr.into(), item.into(), false)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Guards an ink! message so that it fails with
+/// `AccessControlError::ExpectedPause` unless the contract is paused,
+/// like `when_not_paused` does the other way around.
+#[proc_macro_attribute]
+pub fn when_paused(attr: TokenStream, item: TokenStream) -> TokenStream {
+    when_paused::expand(attr.into(), item.into(), true)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements the `AccessControl` trait for an ink! contract, on top
+/// of the `AccessControlData` field of its storage struct.
+///
+/// It has to be placed above `#[ink::contract]`, since it adds the
+/// trait implementation and the `RoleGranted`, `RoleRevoked` and
+/// `RoleAdminChanged` events to the contract module before ink!
+/// processes it. The contract also gets an `AccessControlEvents`
+/// implementation that emits them, which should be passed to the
+/// operations of `AccessControlData`:
+///
+/// ```ignore
+/// #[access_control::access_control]
+/// #[ink::contract]
+/// mod my_contract {
+///     #[ink(storage)]
+///     pub struct MyContract {
+///         access_control: AccessControlData<4>,
+///     }
+///
+///     impl MyContract {
+///         #[ink(constructor)]
+///         pub fn new() -> Self {
+///             let mut access_control = AccessControlData::new();
+///             access_control.set_role::<Self>(Self::env().caller(), DEFAULT_ADMIN_ROLE);
+///             Self { access_control }
+///         }
+///     }
+/// }
+/// ```
+///
+/// The field is found by its type, either `AccessControlData` or
+/// `HashedAccessControlData`, and `field = ...` can be used to pick it
+/// explicitly. For `AccessControlData` the macro also generates:
+///
+/// - the `AccessControlBatch` and `AccessControlIntrospection` traits
+/// - `RoleGrantedUntil`, for time-bound grants
+/// - `RoleGrantScheduled`, `RoleGrantExecuted` and
+///   `RoleGrantCancelled`, for scheduled grants
+/// - `RoleOffered` and `RoleOfferWithdrawn`, for role offers
+/// - the events of the default admin rules
+/// - `RoleSuspended` and `RoleResumed`, for role suspensions
+/// - `AccountFrozen` and `AccountUnfrozen`, for account freezes
+/// - `RoleRevokedFromAll`, for mass revocations
+/// - the `AccessControlEnumerable` trait, with `enumerable`
+///
+/// For `HashedAccessControlData` it checks at compile time that the
+/// `role_id` constants of the module and of its inherent impls, and
+/// `DEFAULT_ADMIN_ROLE_ID`, are distinct.
+///
+/// Other fields of the storage struct add their own items:
+///
+/// - `OwnableData` adds the `Ownable` trait, the
+///   `OwnershipTransferStarted` and `OwnershipTransferred` events and
+///   an `OwnableEvents` implementation. Contracts with an owner and no
+///   roles can use the attribute as well, and keep their owner
+///   messages once they add roles.
+/// - `PausableData` adds the `Pausable` trait, checking its pauser
+///   role against the access control field, and the `Paused` and
+///   `Unpaused` events.
+#[proc_macro_attribute]
+pub fn access_control(attr: TokenStream, item: TokenStream) -> TokenStream {
+    access_control::expand(attr.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements the `Role` trait for a fieldless enum, mapping each
+/// variant to its discriminant as the bit position of the role, and
+/// `TryFrom<usize>`, mapping bit positions back to the variants.
+///
+/// Using the enum with an `AccessControlData<N>` that can't store all
+/// of its variants fails to compile.
+#[proc_macro_derive(Role)]
+pub fn derive_role(item: TokenStream) -> TokenStream {
+    role::expand(item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,82 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Ident, ItemFn, ReturnType, Token,
+};
+
+/// Args are the arguments of an `only_owner` attribute
+pub struct Args {
+    pub field: Ident,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut field = Ident::new("ownable", proc_macro2::Span::call_site());
+
+        if !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key != "field" {
+                return Err(syn::Error::new(key.span(), "expected `field = ...`"));
+            }
+
+            input.parse::<Token![=]>()?;
+            field = input.parse()?;
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        Ok(Args { field })
+    }
+}
+
+pub fn expand(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let args: Args = syn::parse2(attr)?;
+    let mut item: ItemFn = syn::parse2(item)?;
+
+    if matches!(item.sig.output, ReturnType::Default) {
+        return Err(syn::Error::new_spanned(
+            &item.sig,
+            "only_owner can only guard functions returning a Result",
+        ));
+    }
+
+    let field = &args.field;
+    let block = &item.block;
+    item.block = syn::parse_quote!({
+        {
+            let __caller = <Self as ::ink::codegen::StaticEnv>::env().caller();
+            self.#field.ensure_owner(__caller)?;
+        }
+        #block
+    });
+
+    Ok(quote!(#item))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_field() {
+        let args: Args = syn::parse_quote!();
+        assert_eq!(args.field, "ownable");
+
+        let args: Args = syn::parse_quote!(field = owner);
+        assert_eq!(args.field, "owner");
+
+        assert!(syn::parse_str::<Args>("other = owner").is_err());
+    }
+
+    #[test]
+    fn rejects_functions_without_result() {
+        let res = expand(
+            quote!(),
+            quote!(
+                fn flip(&mut self) {}
+            ),
+        );
+
+        assert!(res.is_err());
+    }
+}
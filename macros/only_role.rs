@@ -0,0 +1,149 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Expr, Ident, ItemFn, ReturnType, Token,
+};
+
+/// Roles are the roles required by an `only_role` attribute
+pub enum Roles {
+    One(Box<Expr>),
+    AnyOf(Vec<Expr>),
+    AllOf(Vec<Expr>),
+}
+
+/// Args are the arguments of an `only_role` attribute
+pub struct Args {
+    pub roles: Roles,
+    pub field: Ident,
+}
+
+impl Parse for Roles {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fork = input.fork();
+
+        if let Ok(ident) = fork.parse::<Ident>() {
+            if (ident == "any_of" || ident == "all_of") && fork.peek(syn::token::Paren) {
+                input.parse::<Ident>()?;
+
+                let content;
+                parenthesized!(content in input);
+                let roles: Vec<Expr> = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?
+                    .into_iter()
+                    .collect();
+
+                if roles.is_empty() {
+                    return Err(syn::Error::new(ident.span(), "expected at least one role"));
+                }
+
+                return Ok(if ident == "any_of" {
+                    Roles::AnyOf(roles)
+                } else {
+                    Roles::AllOf(roles)
+                });
+            }
+        }
+
+        Ok(Roles::One(input.parse()?))
+    }
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let roles = input.parse()?;
+        let mut field = Ident::new("access_control", proc_macro2::Span::call_site());
+
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key != "field" {
+                return Err(syn::Error::new(key.span(), "expected `field = ...`"));
+            }
+
+            input.parse::<Token![=]>()?;
+            field = input.parse()?;
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        Ok(Args { roles, field })
+    }
+}
+
+pub fn expand(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let args: Args = syn::parse2(attr)?;
+    let mut item: ItemFn = syn::parse2(item)?;
+
+    if matches!(item.sig.output, ReturnType::Default) {
+        return Err(syn::Error::new_spanned(
+            &item.sig,
+            "only_role can only guard functions returning a Result",
+        ));
+    }
+
+    let field = &args.field;
+    let check = match &args.roles {
+        Roles::One(role) => quote!(ensure_role(__caller, #role)),
+        Roles::AnyOf(roles) => quote!(ensure_any_role(__caller, &[#(#roles),*])),
+        Roles::AllOf(roles) => quote!(ensure_all_roles(__caller, &[#(#roles),*])),
+    };
+
+    let block = &item.block;
+    item.block = syn::parse_quote!({
+        {
+            let __caller = <Self as ::ink::codegen::StaticEnv>::env().caller();
+            self.#field.#check?;
+        }
+        #block
+    });
+
+    Ok(quote!(#item))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_single_role() {
+        let args: Args = syn::parse_quote!(Self::ROLE_1);
+
+        assert!(matches!(args.roles, Roles::One(_)));
+        assert_eq!(args.field, "access_control");
+    }
+
+    #[test]
+    fn parses_any_of_and_all_of() {
+        let args: Args = syn::parse_quote!(any_of(Self::ROLE_1, Self::ROLE_2));
+        assert!(matches!(args.roles, Roles::AnyOf(roles) if roles.len() == 2));
+
+        let args: Args = syn::parse_quote!(all_of(Self::ROLE_1, Self::ROLE_2,));
+        assert!(matches!(args.roles, Roles::AllOf(roles) if roles.len() == 2));
+    }
+
+    #[test]
+    fn parses_field() {
+        let args: Args = syn::parse_quote!(any_of(Self::ROLE_1), field = roles);
+
+        assert!(matches!(args.roles, Roles::AnyOf(_)));
+        assert_eq!(args.field, "roles");
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        assert!(syn::parse_str::<Args>("any_of()").is_err());
+        assert!(syn::parse_str::<Args>("Self::ROLE_1, other = roles").is_err());
+    }
+
+    #[test]
+    fn rejects_functions_without_result() {
+        let res = expand(
+            quote!(Self::ROLE_1),
+            quote!(
+                fn flip(&mut self) {}
+            ),
+        );
+
+        assert!(res.is_err());
+    }
+}
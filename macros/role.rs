@@ -0,0 +1,92 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields};
+
+pub fn expand(item: TokenStream) -> syn::Result<TokenStream> {
+    let input: DeriveInput = syn::parse2(item)?;
+
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Role can only be derived for enums",
+        ));
+    };
+
+    if let Some(variant) = data
+        .variants
+        .iter()
+        .find(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        return Err(syn::Error::new_spanned(
+            variant,
+            "Role can only be derived for enums without fields",
+        ));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variants: Vec<_> = data.variants.iter().map(|variant| &variant.ident).collect();
+
+    // COUNT is the highest discriminant plus one, so that explicit
+    // discriminants are accounted for
+    Ok(quote! {
+        impl #impl_generics ::access_control::Role for #ident #ty_generics #where_clause {
+            const COUNT: usize = {
+                let mut count = 0;
+                #(
+                    if Self::#variants as usize >= count {
+                        count = Self::#variants as usize + 1;
+                    }
+                )*
+                count
+            };
+
+            #[inline]
+            fn index(self) -> usize {
+                self as usize
+            }
+        }
+
+        impl #impl_generics ::core::convert::TryFrom<usize> for #ident #ty_generics #where_clause {
+            type Error = usize;
+
+            fn try_from(index: usize) -> ::core::result::Result<Self, usize> {
+                #(
+                    if index == Self::#variants as usize {
+                        return ::core::result::Result::Ok(Self::#variants);
+                    }
+                )*
+                ::core::result::Result::Err(index)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_non_fieldless_enums() {
+        assert!(expand(quote!(
+            struct Roles;
+        ))
+        .is_err());
+
+        assert!(expand(quote!(
+            enum Roles {
+                Admin,
+                Minter(u8),
+            }
+        ))
+        .is_err());
+
+        assert!(expand(quote!(
+            enum Roles {
+                Admin,
+                Minter,
+            }
+        ))
+        .is_ok());
+    }
+}
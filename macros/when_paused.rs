@@ -0,0 +1,89 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Ident, ItemFn, ReturnType, Token,
+};
+
+/// Args are the arguments of a `when_paused` or `when_not_paused`
+/// attribute
+pub struct Args {
+    pub field: Ident,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut field = Ident::new("pausable", proc_macro2::Span::call_site());
+
+        if !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key != "field" {
+                return Err(syn::Error::new(key.span(), "expected `field = ...`"));
+            }
+
+            input.parse::<Token![=]>()?;
+            field = input.parse()?;
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        Ok(Args { field })
+    }
+}
+
+/// expand guards the message so that it only runs while the contract
+/// is paused if `paused` is true, or while it isn't otherwise
+pub fn expand(attr: TokenStream, item: TokenStream, paused: bool) -> syn::Result<TokenStream> {
+    let args: Args = syn::parse2(attr)?;
+    let mut item: ItemFn = syn::parse2(item)?;
+
+    if matches!(item.sig.output, ReturnType::Default) {
+        return Err(syn::Error::new_spanned(
+            &item.sig,
+            "pause guards can only guard functions returning a Result",
+        ));
+    }
+
+    let field = &args.field;
+    let check = if paused {
+        quote!(when_paused)
+    } else {
+        quote!(when_not_paused)
+    };
+
+    let block = &item.block;
+    item.block = syn::parse_quote!({
+        self.#field.#check()?;
+        #block
+    });
+
+    Ok(quote!(#item))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_field() {
+        let args: Args = syn::parse_quote!();
+        assert_eq!(args.field, "pausable");
+
+        let args: Args = syn::parse_quote!(field = stop);
+        assert_eq!(args.field, "stop");
+
+        assert!(syn::parse_str::<Args>("other = stop").is_err());
+    }
+
+    #[test]
+    fn rejects_functions_without_result() {
+        let res = expand(
+            quote!(),
+            quote!(
+                fn flip(&mut self) {}
+            ),
+            false,
+        );
+
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{AccessControlError, BitMap, Role};
+
+/// RoleMask is a set of roles laid out like the roles of an account in
+/// an `AccessControlData<N, PAGES>`, so that it can be compared
+/// against them or replace them as a whole.
+///
+/// ```ignore
+/// let mask = RoleMask::<4>::new().with(Roles::Minter).with(Roles::Burner);
+/// access_control.set_roles::<Self>(account, &mask);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoleMask<const N: usize, const PAGES: usize = 1> {
+    pub(crate) pages: [BitMap<N>; PAGES],
+}
+
+impl<const N: usize, const PAGES: usize> Default for RoleMask<N, PAGES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, const PAGES: usize> RoleMask<N, PAGES> {
+    /// new returns a mask without any role
+    pub const fn new() -> Self {
+        RoleMask {
+            pages: [BitMap::new(); PAGES],
+        }
+    }
+
+    /// of returns a mask with every role of `roles`. Being a const fn
+    /// it can build masks at compile time, in which case roles that
+    /// don't fit in the mask fail to compile:
+    ///
+    /// ```
+    /// use access_control::RoleMask;
+    ///
+    /// const MANAGERS: RoleMask<4> = RoleMask::of(&[1, 2]);
+    /// assert!(MANAGERS.has(1) && MANAGERS.has(2));
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use access_control::RoleMask;
+    ///
+    /// // 8 doesn't fit in a single byte
+    /// const MANAGERS: RoleMask<1> = RoleMask::of(&[1, 8]);
+    /// assert!(MANAGERS.has(1));
+    /// ```
+    ///
+    /// Roles deriving `Role` can be given as `Roles::Minter as usize`.
+    pub const fn of(roles: &[usize]) -> Self {
+        let mut mask = Self::new();
+        let mut i = 0;
+
+        while i < roles.len() {
+            mask = mask.with_index(roles[i]);
+            i += 1;
+        }
+
+        mask
+    }
+
+    /// with_index is the const version of with, taking the bit
+    /// position of the role
+    pub const fn with_index(mut self, role: usize) -> Self {
+        assert!(role < N * 8 * PAGES, "role out of range of the mask");
+
+        self.pages[role / (N * 8)].set_bit(role % (N * 8));
+        self
+    }
+
+    /// from_roles returns a mask with every role of `roles`, panicking
+    /// if any of them doesn't fit in it
+    pub fn from_roles(roles: &[impl Role]) -> Self {
+        roles.iter().fold(Self::new(), |mask, role| mask.with(*role))
+    }
+
+    /// try_from_roles is the checked version of from_roles, it fails
+    /// with `RoleOutOfRange` if any of `roles` doesn't fit in the mask
+    pub fn try_from_roles(roles: &[impl Role]) -> Result<Self, AccessControlError> {
+        roles
+            .iter()
+            .try_fold(Self::new(), |mask, role| mask.try_with(*role))
+    }
+
+    /// with returns the mask with `role` added, panicking if it
+    /// doesn't fit in it
+    pub fn with(self, role: impl Role) -> Self {
+        self.try_with(role)
+            .unwrap_or_else(|_| panic!("role out of range of the mask"))
+    }
+
+    /// try_with is the checked version of with
+    pub fn try_with(mut self, role: impl Role) -> Result<Self, AccessControlError> {
+        let (page, bit) = Self::locate(Self::index(role))?;
+        self.pages[page].set_bit(bit);
+        Ok(self)
+    }
+
+    /// has returns true if `role` is in the mask
+    pub fn has(&self, role: impl Role) -> bool {
+        match Self::locate(Self::index(role)) {
+            Ok((page, bit)) => self.pages[page].has_bit_set(bit),
+            Err(_) => false,
+        }
+    }
+
+    /// iter returns the bit positions of the roles in the mask, in
+    /// ascending order
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.pages
+            .iter()
+            .enumerate()
+            .flat_map(|(page, roles)| roles.iter_ones().map(move |bit| page * N * 8 + bit))
+    }
+
+    /// is_empty returns true if the mask doesn't have any role
+    pub fn is_empty(&self) -> bool {
+        self.pages.iter().all(BitMap::is_empty)
+    }
+
+    fn index<R: Role>(role: R) -> usize {
+        const {
+            assert!(
+                R::COUNT <= N * 8 * PAGES,
+                "the role type has more roles than RoleMask can store"
+            );
+        }
+
+        role.index()
+    }
+
+    fn locate(role: usize) -> Result<(usize, usize), AccessControlError> {
+        if role >= N * 8 * PAGES {
+            return Err(AccessControlError::RoleOutOfRange);
+        }
+
+        Ok((role / (N * 8), role % (N * 8)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn of_works() {
+        const MASK: RoleMask<1, 2> = RoleMask::of(&[1, 9]);
+
+        assert_eq!(MASK, RoleMask::from_roles(&[9, 1]));
+        assert_eq!(RoleMask::<1, 2>::of(&[]), RoleMask::new());
+    }
+
+    #[test]
+    fn with_and_has_work() {
+        let mask = RoleMask::<1, 2>::new().with(1).with(9);
+
+        assert!(mask.has(1));
+        assert!(mask.has(9));
+        assert!(!mask.has(2));
+        assert!(!mask.has(16));
+        assert!(mask.pages[0].has_bit_set(1));
+        assert!(mask.pages[1].has_bit_set(1));
+        assert!(!mask.is_empty());
+        assert!(RoleMask::<1, 2>::new().is_empty());
+    }
+
+    #[test]
+    fn iter_works() {
+        let mask = RoleMask::<1, 3>::of(&[17, 0, 7, 20]);
+
+        assert_eq!(mask.iter().collect::<Vec<_>>(), [0, 7, 17, 20]);
+        assert_eq!(RoleMask::<1>::new().iter().count(), 0);
+    }
+
+    #[test]
+    fn from_roles_fails_out_of_range() {
+        assert_eq!(
+            RoleMask::<1>::try_from_roles(&[1, 8]),
+            Err(AccessControlError::RoleOutOfRange)
+        );
+        assert_eq!(RoleMask::<1>::try_from_roles(&[1, 7]), Ok(RoleMask::<1>::from_roles(&[7, 1])));
+    }
+}
@@ -0,0 +1,213 @@
+use ink::primitives::AccountId;
+
+use crate::{AccessControlError, OwnableEvents, OwnershipTransferStarted, OwnershipTransferred};
+
+/// OwnableData is the simplest access control there is, a single
+/// account owning the contract. It's meant for contracts that don't
+/// need roles, and fails with the same `AccessControlError` as
+/// `AccessControlData` so that a contract can move from one to the
+/// other without changing its messages.
+///
+/// Ownership is transferred in two steps, the owner proposes a new
+/// owner with `transfer_ownership` and it has to accept it with
+/// `accept_ownership`, so that it can't be transferred to an account
+/// nobody controls.
+#[derive(Debug)]
+#[ink::storage_item]
+pub struct OwnableData {
+    /// The account owning the contract, if any.
+    pub owner: Option<AccountId>,
+
+    /// The account the owner is transferring the ownership to, until
+    /// it accepts it.
+    pub pending_owner: Option<AccountId>,
+}
+
+impl Default for OwnableData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OwnableData {
+    /// new returns an OwnableData without owner, which should be set
+    /// with `set_owner` in the constructor of the contract
+    pub fn new() -> Self {
+        OwnableData {
+            owner:         None,
+            pending_owner: None,
+        }
+    }
+
+    /// set_owner makes `owner` the owner without any authorization,
+    /// forgetting any pending transfer and emitting
+    /// `OwnershipTransferred` through `E` if the owner changed
+    pub fn set_owner<E: OwnableEvents>(&mut self, owner: AccountId) {
+        self.pending_owner = None;
+        self.replace_owner::<E>(Some(owner));
+    }
+
+    /// owner returns the owner of the contract, or None if the
+    /// ownership was renounced
+    pub fn owner(&self) -> Option<AccountId> {
+        self.owner
+    }
+
+    /// pending_owner returns the account the ownership is being
+    /// transferred to, if any
+    pub fn pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner
+    }
+
+    /// is_owner returns true if `account_id` is the owner
+    pub fn is_owner(&self, account_id: AccountId) -> bool {
+        self.owner == Some(account_id)
+    }
+
+    /// ensure_owner fails with `NotOwner` if `account_id` isn't the
+    /// owner
+    pub fn ensure_owner(&self, account_id: AccountId) -> Result<(), AccessControlError> {
+        if !self.is_owner(account_id) {
+            return Err(AccessControlError::NotOwner {
+                account: account_id,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// transfer_ownership starts transferring the ownership to
+    /// `new_owner` if `caller` is the owner. The owner doesn't change
+    /// until `new_owner` accepts it, and transferring it again replaces
+    /// the pending owner.
+    pub fn transfer_ownership<E: OwnableEvents>(
+        &mut self,
+        caller: AccountId,
+        new_owner: AccountId,
+    ) -> Result<(), AccessControlError> {
+        self.ensure_owner(caller)?;
+        self.pending_owner = Some(new_owner);
+
+        E::emit_ownership_transfer_started(OwnershipTransferStarted {
+            previous_owner: caller,
+            new_owner,
+        });
+
+        Ok(())
+    }
+
+    /// accept_ownership makes `caller` the owner if the ownership is
+    /// being transferred to it, otherwise it fails with
+    /// `NotPendingOwner`
+    pub fn accept_ownership<E: OwnableEvents>(
+        &mut self,
+        caller: AccountId,
+    ) -> Result<(), AccessControlError> {
+        if self.pending_owner != Some(caller) {
+            return Err(AccessControlError::NotPendingOwner { account: caller });
+        }
+
+        self.pending_owner = None;
+        self.replace_owner::<E>(Some(caller));
+        Ok(())
+    }
+
+    /// renounce_ownership leaves the contract without owner if `caller`
+    /// is the owner, forgetting any pending transfer. Nobody can pass
+    /// `ensure_owner` afterwards.
+    pub fn renounce_ownership<E: OwnableEvents>(
+        &mut self,
+        caller: AccountId,
+    ) -> Result<(), AccessControlError> {
+        self.ensure_owner(caller)?;
+
+        self.pending_owner = None;
+        self.replace_owner::<E>(None);
+        Ok(())
+    }
+
+    fn replace_owner<E: OwnableEvents>(&mut self, new_owner: Option<AccountId>) {
+        let previous_owner = core::mem::replace(&mut self.owner, new_owner);
+
+        if previous_owner != new_owner {
+            E::emit_ownership_transferred(OwnershipTransferred {
+                previous_owner,
+                new_owner,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (OwnableData, AccountId, AccountId) {
+        let (owner, other) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+        let mut ownable = OwnableData::new();
+
+        assert_eq!(ownable.owner(), None);
+        ownable.set_owner::<()>(owner);
+
+        (ownable, owner, other)
+    }
+
+    #[ink::test]
+    fn ensure_owner_works() {
+        let (ownable, owner, other) = setup();
+
+        assert_eq!(ownable.ensure_owner(owner), Ok(()));
+        assert_eq!(
+            ownable.ensure_owner(other),
+            Err(AccessControlError::NotOwner { account: other })
+        );
+    }
+
+    #[ink::test]
+    fn ownership_is_transferred_on_acceptance() {
+        let (mut ownable, owner, other) = setup();
+
+        assert_eq!(
+            ownable.transfer_ownership::<()>(other, other),
+            Err(AccessControlError::NotOwner { account: other })
+        );
+
+        assert_eq!(ownable.transfer_ownership::<()>(owner, other), Ok(()));
+        assert_eq!(ownable.owner(), Some(owner));
+        assert_eq!(ownable.pending_owner(), Some(other));
+
+        // only the pending owner can accept it
+        assert_eq!(
+            ownable.accept_ownership::<()>(owner),
+            Err(AccessControlError::NotPendingOwner { account: owner })
+        );
+
+        assert_eq!(ownable.accept_ownership::<()>(other), Ok(()));
+        assert_eq!(ownable.owner(), Some(other));
+        assert_eq!(ownable.pending_owner(), None);
+        assert_eq!(
+            ownable.ensure_owner(owner),
+            Err(AccessControlError::NotOwner { account: owner })
+        );
+    }
+
+    #[ink::test]
+    fn renounce_ownership_works() {
+        let (mut ownable, owner, other) = setup();
+
+        ownable.transfer_ownership::<()>(owner, other).unwrap();
+        assert_eq!(
+            ownable.renounce_ownership::<()>(other),
+            Err(AccessControlError::NotOwner { account: other })
+        );
+
+        assert_eq!(ownable.renounce_ownership::<()>(owner), Ok(()));
+        assert_eq!(ownable.owner(), None);
+
+        // the pending transfer is forgotten too
+        assert_eq!(
+            ownable.accept_ownership::<()>(other),
+            Err(AccessControlError::NotPendingOwner { account: other })
+        );
+    }
+}
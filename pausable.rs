@@ -0,0 +1,180 @@
+use ink::primitives::AccountId;
+
+use crate::{
+    AccessControlData, AccessControlError, HashedAccessControlData, PausableEvents, Paused, Role,
+    RoleId, Unpaused,
+};
+
+/// HasRoles is implemented by the access control data types so that
+/// other storage items, like `PausableData`, can check the roles of an
+/// account without depending on their layout.
+pub trait HasRoles {
+    /// check_role_id fails with `MissingRole` if `account_id` doesn't
+    /// hold `role`, given as the `RoleId` of contract messages
+    fn check_role_id(&self, account_id: AccountId, role: RoleId) -> Result<(), AccessControlError>;
+}
+
+impl<const N: usize, const PAGES: usize> HasRoles for AccessControlData<N, PAGES> {
+    fn check_role_id(&self, account_id: AccountId, role: RoleId) -> Result<(), AccessControlError> {
+        self.ensure_role(account_id, role as usize)
+    }
+}
+
+impl HasRoles for HashedAccessControlData {
+    fn check_role_id(&self, account_id: AccountId, role: RoleId) -> Result<(), AccessControlError> {
+        self.ensure_role(account_id, role)
+    }
+}
+
+/// PausableData is an emergency stop for a contract. Messages guarded
+/// with `when_not_paused` fail while it's paused, and the ones guarded
+/// with `when_paused` fail while it isn't.
+///
+/// Only the accounts holding its pauser role in the contract's access
+/// control data can pause and unpause it:
+///
+/// ```ignore
+/// let pausable = PausableData::new(Roles::Pauser);
+/// self.pausable.pause::<Self>(&self.access_control, caller)?;
+/// ```
+#[derive(Debug)]
+#[ink::storage_item]
+pub struct PausableData {
+    /// Whether the contract is paused.
+    pub paused: bool,
+
+    /// The role required to pause and unpause the contract.
+    pub pauser_role: RoleId,
+}
+
+impl PausableData {
+    /// new returns an unpaused PausableData that can be paused by the
+    /// accounts holding `pauser_role`. With `HashedAccessControlData`
+    /// the role is its `RoleId` as usize.
+    pub fn new(pauser_role: impl Role) -> Self {
+        PausableData {
+            paused:      false,
+            pauser_role: pauser_role.index() as RoleId,
+        }
+    }
+
+    /// is_paused returns true if the contract is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// when_not_paused fails with `EnforcedPause` if the contract is
+    /// paused
+    pub fn when_not_paused(&self) -> Result<(), AccessControlError> {
+        if self.paused {
+            return Err(AccessControlError::EnforcedPause);
+        }
+
+        Ok(())
+    }
+
+    /// when_paused fails with `ExpectedPause` if the contract isn't
+    /// paused
+    pub fn when_paused(&self) -> Result<(), AccessControlError> {
+        if !self.paused {
+            return Err(AccessControlError::ExpectedPause);
+        }
+
+        Ok(())
+    }
+
+    /// pause pauses the contract if `caller` holds the pauser role in
+    /// `roles` and it isn't paused already
+    pub fn pause<E: PausableEvents>(
+        &mut self,
+        roles: &impl HasRoles,
+        caller: AccountId,
+    ) -> Result<(), AccessControlError> {
+        roles.check_role_id(caller, self.pauser_role)?;
+        self.when_not_paused()?;
+
+        self.paused = true;
+        E::emit_paused(Paused { account: caller });
+        Ok(())
+    }
+
+    /// unpause unpauses the contract if `caller` holds the pauser role
+    /// in `roles` and it's paused
+    pub fn unpause<E: PausableEvents>(
+        &mut self,
+        roles: &impl HasRoles,
+        caller: AccountId,
+    ) -> Result<(), AccessControlError> {
+        roles.check_role_id(caller, self.pauser_role)?;
+        self.when_paused()?;
+
+        self.paused = false;
+        E::emit_unpaused(Unpaused { account: caller });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAUSER: usize = 1;
+
+    fn setup() -> (PausableData, AccessControlData<4>, AccountId, AccountId) {
+        let mut access_control = AccessControlData::<4>::new();
+        let (pauser, other) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+
+        access_control.set_role::<()>(pauser, PAUSER);
+        (PausableData::new(PAUSER), access_control, pauser, other)
+    }
+
+    #[ink::test]
+    fn pause_and_unpause_work() {
+        let (mut pausable, access_control, pauser, _) = setup();
+
+        assert_eq!(pausable.when_not_paused(), Ok(()));
+        assert_eq!(pausable.when_paused(), Err(AccessControlError::ExpectedPause));
+        assert_eq!(
+            pausable.unpause::<()>(&access_control, pauser),
+            Err(AccessControlError::ExpectedPause)
+        );
+
+        assert_eq!(pausable.pause::<()>(&access_control, pauser), Ok(()));
+        assert!(pausable.is_paused());
+        assert_eq!(pausable.when_not_paused(), Err(AccessControlError::EnforcedPause));
+        assert_eq!(pausable.when_paused(), Ok(()));
+        assert_eq!(
+            pausable.pause::<()>(&access_control, pauser),
+            Err(AccessControlError::EnforcedPause)
+        );
+
+        assert_eq!(pausable.unpause::<()>(&access_control, pauser), Ok(()));
+        assert!(!pausable.is_paused());
+    }
+
+    #[ink::test]
+    fn only_pausers_can_pause() {
+        let (mut pausable, access_control, pauser, other) = setup();
+        let missing_role = Err(AccessControlError::MissingRole {
+            account: other,
+            role:    PAUSER as RoleId,
+        });
+
+        assert_eq!(pausable.pause::<()>(&access_control, other), missing_role);
+
+        pausable.pause::<()>(&access_control, pauser).unwrap();
+        assert_eq!(pausable.unpause::<()>(&access_control, other), missing_role);
+    }
+
+    #[ink::test]
+    fn works_with_hashed_roles() {
+        let mut access_control = HashedAccessControlData::new();
+        let pauser = AccountId::from([1u8; 32]);
+        let role = crate::role_id("PAUSER");
+
+        access_control.set_role::<()>(pauser, role);
+        let mut pausable = PausableData::new(role as usize);
+
+        assert_eq!(pausable.pause::<()>(&access_control, pauser), Ok(()));
+    }
+}
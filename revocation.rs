@@ -0,0 +1,283 @@
+use ink::primitives::AccountId;
+
+use crate::{
+    AccessControlData, AccessControlError, AccessControlEvents, BitMap, Role, RoleId,
+    RoleRevokedFromAll,
+};
+
+impl<const N: usize, const PAGES: usize> AccessControlData<N, PAGES> {
+    /// revoke_role_from_all revokes `role` from every account holding
+    /// it if `caller` holds the admin role of `role`, emitting
+    /// `RoleRevokedFromAll` through `E`.
+    ///
+    /// The holders can't be listed, so instead of clearing their bits
+    /// it starts a new generation of `role`, and the grants made in
+    /// the previous ones stop counting. It costs the same however many
+    /// accounts hold the role, and the bits left behind, along with
+    /// their expiries, scheduled grants and member slots, are cleared
+    /// the next time the roles of each account change. Granting the role again makes
+    /// it count in the new generation.
+    pub fn revoke_role_from_all<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        role: impl Role,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+        Self::check_role(role)?;
+        self.check_role_admin(caller, role)?;
+
+        let generation = self.role_generation(role) + 1;
+        if self
+            .role_generations
+            .insert(role as RoleId, &generation)
+            .is_none()
+        {
+            self.revoked_roles += 1;
+        }
+
+        // the new members are indexed from scratch, over the old ones,
+        // and the slots they don't take are removed by clear_stale
+        if self.enumerable {
+            self.role_member_counts.remove(role as RoleId);
+        }
+
+        E::emit_role_revoked_from_all(RoleRevokedFromAll {
+            role: role as RoleId,
+            generation,
+            sender: caller,
+        });
+
+        Ok(())
+    }
+
+    /// role_generation returns the number of times `role` was revoked
+    /// from every account with `revoke_role_from_all`
+    pub fn role_generation(&self, role: impl Role) -> u32 {
+        if self.revoked_roles == 0 {
+            return 0;
+        }
+
+        self.role_generations
+            .get(Self::index(role) as RoleId)
+            .unwrap_or(0)
+    }
+
+    /// is_stale returns true if `role` was granted to `account_id`
+    /// before it was last revoked from every account
+    pub(crate) fn is_stale(&self, account_id: AccountId, role: usize) -> bool {
+        if self.revoked_roles == 0 {
+            return false;
+        }
+
+        let Some(generation) = self.role_generations.get(role as RoleId) else {
+            return false;
+        };
+
+        self.grant_generations
+            .get((account_id, role as RoleId))
+            .unwrap_or(0)
+            < generation
+    }
+
+    /// record_grant stores the generation `role` is granted to
+    /// `account_id` in. Roles that were never revoked from every
+    /// account are in generation 0, which isn't stored.
+    pub(crate) fn record_grant(&mut self, account_id: AccountId, role: usize) {
+        let generation = self.role_generation(role);
+
+        if generation > 0 {
+            self.grant_generations
+                .insert((account_id, role as RoleId), &generation);
+        }
+    }
+
+    /// forget_grant removes the generation `role` was granted to
+    /// `account_id` in, if it was stored
+    pub(crate) fn forget_grant(&mut self, account_id: AccountId, role: usize) {
+        if self.revoked_roles > 0 {
+            self.grant_generations.remove((account_id, role as RoleId));
+        }
+    }
+
+    /// stale_roles returns the roles in `roles`, a page of the roles of
+    /// `account_id`, that are stale
+    pub(crate) fn stale_roles(
+        &self,
+        account_id: AccountId,
+        page: u32,
+        roles: &BitMap<N>,
+    ) -> BitMap<N> {
+        let mut stale = BitMap::new();
+
+        if self.revoked_roles > 0 {
+            for bit in roles.iter_ones() {
+                if self.is_stale(account_id, page as usize * N * 8 + bit) {
+                    stale.set_bit(bit);
+                }
+            }
+        }
+
+        stale
+    }
+
+    /// clear_stale reads a page of the roles of `account_id` to change
+    /// it, clearing its stale roles along with what's left of their
+    /// grants. No events are emitted for them, `RoleRevokedFromAll`
+    /// already covered them.
+    pub(crate) fn clear_stale(&mut self, account_id: AccountId, page: u32) -> BitMap<N> {
+        let roles = self
+            .roles_per_account
+            .get((account_id, page))
+            .unwrap_or_default();
+        let stale = self.stale_roles(account_id, page, &roles);
+
+        if stale.is_empty() {
+            return roles;
+        }
+
+        for bit in stale.iter_ones() {
+            let role = page as usize * N * 8 + bit;
+
+            self.clear_expiry(account_id, role);
+            self.take_pending_grant(account_id, role);
+            self.forget_grant(account_id, role);
+
+            if self.enumerable {
+                self.forget_stale_member(role as RoleId, account_id);
+            }
+        }
+
+        let roles = roles.difference(&stale);
+        self.store_page(account_id, page, &roles);
+        roles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Expiry, RoleMask, DEFAULT_ADMIN_ROLE};
+    use ink::env::{test::set_block_timestamp, DefaultEnvironment};
+
+    const ROLE: usize = 1;
+
+    fn setup() -> (AccessControlData<4>, AccountId, [AccountId; 3]) {
+        let mut access_control = AccessControlData::<4>::new_enumerable();
+        let admin = AccountId::from([1u8; 32]);
+        let holders = [2u8, 3, 4].map(|byte| AccountId::from([byte; 32]));
+
+        access_control.set_role::<()>(admin, DEFAULT_ADMIN_ROLE);
+        for holder in holders {
+            access_control.set_account_roles::<()>(holder, &[ROLE, 2]);
+        }
+
+        (access_control, admin, holders)
+    }
+
+    #[ink::test]
+    fn revoked_roles_arent_held_by_anyone() {
+        let (mut access_control, admin, [alice, bob, charlie]) = setup();
+
+        assert_eq!(access_control.revoke_role_from_all::<()>(admin, ROLE), Ok(()));
+        assert_eq!(access_control.role_generation(ROLE), 1);
+        assert_eq!(access_control.role_generation(2), 0);
+
+        for holder in [alice, bob, charlie] {
+            assert!(!access_control.has_role(holder, ROLE));
+            assert!(access_control.has_role(holder, 2));
+            assert!(!access_control.has_any_of(holder, &RoleMask::of(&[ROLE])));
+            assert_eq!(access_control.roles_of(holder), [2]);
+        }
+        assert_eq!(access_control.get_role_member_count(ROLE), 0);
+        assert_eq!(access_control.get_role_member(ROLE, 0), None);
+
+        // granting it again counts in the new generation
+        access_control.grant_role::<()>(admin, bob, ROLE).unwrap();
+        assert!(access_control.has_role(bob, ROLE));
+        assert!(!access_control.has_role(alice, ROLE));
+        assert_eq!(access_control.role_members(ROLE, 0, 10), [bob]);
+
+        access_control.revoke_role::<()>(admin, bob, ROLE).unwrap();
+        assert!(!access_control.has_role(bob, ROLE));
+        assert_eq!(access_control.get_role_member_count(ROLE), 0);
+    }
+
+    #[ink::test]
+    fn stale_roles_are_cleared_on_the_next_write() {
+        let (mut access_control, admin, [alice, bob, _]) = setup();
+
+        access_control.set_role_until::<()>(alice, 3, Expiry::Timestamp(1_000));
+        access_control.revoke_role_from_all::<()>(admin, 3).unwrap();
+        access_control.revoke_role_from_all::<()>(admin, ROLE).unwrap();
+        assert!(access_control.roles_per_account.get((alice, 0)).unwrap().has_bit_set(ROLE));
+
+        // revoking another role clears them without any event
+        access_control.unset_role::<()>(alice, 2);
+        assert!(!access_control.roles_per_account.contains((alice, 0)));
+        assert!(!access_control.role_expiries.contains((alice, 3 as RoleId)));
+        assert!(!access_control.role_member_positions.contains((ROLE as RoleId, alice)));
+        assert_eq!(access_control.timed_grants, 0);
+
+        // and so does granting the revoked role again
+        access_control.set_role::<()>(bob, ROLE);
+        assert_eq!(
+            access_control.roles_per_account.get((bob, 0)),
+            Some(BitMap::from_bytes([6, 0, 0, 0]))
+        );
+        assert_eq!(access_control.grant_generations.get((bob, ROLE as RoleId)), Some(1));
+    }
+
+    #[ink::test]
+    fn stale_members_are_removed_from_the_index() {
+        let (mut access_control, admin, [alice, bob, charlie]) = setup();
+        let slot = |index: u32| (ROLE as RoleId, index);
+
+        access_control.revoke_role_from_all::<()>(admin, ROLE).unwrap();
+
+        // bob takes the slot of alice and leaves his own
+        access_control.grant_role::<()>(admin, bob, ROLE).unwrap();
+        assert_eq!(access_control.role_members.get(slot(0)), Some(bob));
+        assert!(!access_control.role_members.contains(slot(1)));
+
+        // which is left to bob as well when alice's roles change
+        access_control.unset_role::<()>(alice, 2);
+        assert_eq!(access_control.role_members.get(slot(0)), Some(bob));
+        assert!(!access_control.role_member_positions.contains((ROLE as RoleId, alice)));
+
+        access_control.unset_role::<()>(charlie, 2);
+        assert!(!access_control.role_members.contains(slot(2)));
+        assert_eq!(access_control.role_members(ROLE, 0, 10), [bob]);
+    }
+
+    #[ink::test]
+    fn scheduled_grants_are_revoked_too() {
+        let (mut access_control, admin, _) = setup();
+        let account = AccountId::from([9u8; 32]);
+
+        set_block_timestamp::<DefaultEnvironment>(1_000);
+        access_control.schedule_grant::<()>(admin, account, 3, 100).unwrap();
+        access_control.revoke_role_from_all::<()>(admin, 3).unwrap();
+
+        set_block_timestamp::<DefaultEnvironment>(1_100);
+        assert!(!access_control.has_role(account, 3));
+        assert_eq!(
+            access_control.execute_grant::<()>(account, 3),
+            Err(AccessControlError::GrantNotPending)
+        );
+        assert_eq!(access_control.pending_count, 0);
+    }
+
+    #[ink::test]
+    fn only_admins_can_revoke_from_all() {
+        let (mut access_control, _, [alice, ..]) = setup();
+
+        assert_eq!(
+            access_control.revoke_role_from_all::<()>(alice, ROLE),
+            Err(AccessControlError::MissingRole {
+                account: alice,
+                role:    DEFAULT_ADMIN_ROLE as RoleId,
+            })
+        );
+        assert!(access_control.has_role(alice, ROLE));
+    }
+}
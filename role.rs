@@ -0,0 +1,99 @@
+/// Role is implemented by the types that identify roles in
+/// `AccessControlData`, mapping each role to its bit position.
+///
+/// It's implemented for `usize`, where the value is the bit position
+/// itself, and can be derived for fieldless enums, where each variant
+/// is mapped to its discriminant:
+///
+/// ```ignore
+/// #[derive(Clone, Copy, Role)]
+/// enum Roles {
+///     Admin,
+///     Minter,
+///     Burner,
+/// }
+///
+/// access_control.set_role::<Self>(account, Roles::Minter);
+/// ```
+///
+/// The derive implements `TryFrom<usize>` as well, so that
+/// `roles_of_as` can return the roles of an account as the enum.
+///
+/// Using an enum with more roles than an `AccessControlData<N>` can
+/// store fails to compile:
+///
+/// ```compile_fail
+/// use access_control::{AccessControlData, Role};
+/// use ink::primitives::AccountId;
+///
+/// #[derive(Clone, Copy, Role)]
+/// enum Roles { R0, R1, R2, R3, R4, R5, R6, R7, R8 }
+///
+/// // 9 roles don't fit in a single byte
+/// let mut access_control = AccessControlData::<1>::new();
+/// access_control.set_role::<()>(AccountId::from([1u8; 32]), Roles::R0);
+/// ```
+pub trait Role: Copy {
+    /// COUNT is the number of bit positions the type needs, or 0 if
+    /// it's not known at compile time.
+    const COUNT: usize;
+
+    /// index returns the bit position of the role
+    fn index(self) -> usize;
+}
+
+impl Role for usize {
+    const COUNT: usize = 0;
+
+    #[inline]
+    fn index(self) -> usize {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AccessControlData, Role};
+    use ink::primitives::AccountId;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Role)]
+    enum Roles {
+        Admin,
+        Minter,
+        Burner = 7,
+    }
+
+    #[test]
+    fn derive_role_works() {
+        assert_eq!(Roles::Admin.index(), 0);
+        assert_eq!(Roles::Minter.index(), 1);
+        assert_eq!(Roles::Burner.index(), 7);
+        assert_eq!(Roles::COUNT, 8);
+    }
+
+    #[ink::test]
+    fn enum_roles_work() {
+        let mut access_control = AccessControlData::<1>::new();
+        let account = AccountId::from([1u8; 32]);
+
+        access_control.set_role::<()>(account, Roles::Burner);
+
+        assert!(access_control.has_role(account, Roles::Burner));
+        assert!(access_control.has_role(account, 7));
+        assert!(!access_control.has_role(account, Roles::Minter));
+    }
+
+    #[ink::test]
+    fn roles_are_listed_as_the_enum() {
+        let mut access_control = AccessControlData::<1>::new();
+        let account = AccountId::from([1u8; 32]);
+
+        assert_eq!(Roles::try_from(7), Ok(Roles::Burner));
+        assert_eq!(Roles::try_from(2), Err(2));
+
+        // bit 2 isn't a variant, so it's left out
+        access_control.set_account_roles::<()>(account, &[7, 2, 0]);
+        assert_eq!(access_control.roles_of_as::<Roles>(account), [Roles::Admin, Roles::Burner]);
+        assert_eq!(access_control.roles_of_as::<usize>(account), [0, 2, 7]);
+    }
+}
@@ -0,0 +1,402 @@
+use ink::{env::DefaultEnvironment, primitives::AccountId};
+
+use crate::{
+    expiry::Timestamp, AccessControlData, AccessControlError, AccessControlEvents, Role,
+    RoleGrantExecuted, RoleGrantScheduled, RoleId,
+};
+
+impl<const N: usize, const PAGES: usize> AccessControlData<N, PAGES> {
+    /// set_grant_delay sets the minimum delay, in milliseconds, of the
+    /// scheduled grants of `role`. Roles with a delay can't be granted
+    /// directly by their admins, they have to be scheduled with
+    /// `schedule_grant`.
+    ///
+    /// Like `set_role_admin` it doesn't perform any authorization,
+    /// it's meant to be used while setting up the contract or behind
+    /// the contract's own checks.
+    pub fn set_grant_delay(&mut self, role: impl Role, delay: Timestamp) {
+        let role = Self::index(role) as RoleId;
+        let had_delay = self.grant_delays.contains(role);
+
+        match (had_delay, delay) {
+            (false, 0) => {}
+            (true, 0) => {
+                self.grant_delays.remove(role);
+                self.delayed_roles -= 1;
+            }
+            (had_delay, delay) => {
+                self.grant_delays.insert(role, &delay);
+                self.delayed_roles += u32::from(!had_delay);
+            }
+        }
+    }
+
+    /// get_grant_delay returns the minimum delay, in milliseconds, of
+    /// the scheduled grants of `role`
+    pub fn get_grant_delay(&self, role: impl Role) -> Timestamp {
+        if self.delayed_roles == 0 {
+            return 0;
+        }
+
+        self.grant_delays.get(Self::index(role) as RoleId).unwrap_or(0)
+    }
+
+    /// schedule_grant grants `role` to `account_id` once `delay`
+    /// milliseconds have passed, if `caller` holds the admin role of
+    /// `role` and `delay` is at least the role's minimum delay. Until
+    /// then, `has_role` and the rest of queries ignore the grant and the
+    /// admins can cancel it with `cancel_grant`.
+    ///
+    /// Scheduling a grant that's already scheduled reschedules it, and
+    /// scheduling a role the account already holds doesn't do anything.
+    /// An expired grant of the role is revoked first, emitting
+    /// `RoleRevoked`. Roles requiring acceptance can't be scheduled.
+    pub fn schedule_grant<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        account_id: AccountId,
+        role: impl Role,
+        delay: Timestamp,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+        Self::check_role(role)?;
+        self.check_role_admin(caller, role)?;
+        self.check_acceptance(role)?;
+
+        if delay < self.get_grant_delay(role) {
+            return Err(AccessControlError::DelayTooShort);
+        }
+
+        // an expired grant ends before the new one is scheduled
+        if self.is_expired(account_id, role) {
+            self.unset_role_by::<E>(caller, account_id, role);
+        }
+
+        let (page, bit) = Self::page_of(role);
+        let mut account_roles = self.clear_stale(account_id, page);
+
+        if account_roles.has_bit_set(bit) && !self.is_inactive(account_id, role) {
+            return Ok(());
+        }
+
+        let ready_at = ink::env::block_timestamp::<DefaultEnvironment>().saturating_add(delay);
+
+        // the role is stored as held right away, it's the pending
+        // grant that keeps the queries from seeing it until it matures
+        account_roles.set_bit(bit);
+        self.roles_per_account.insert((account_id, page), &account_roles);
+        self.record_grant(account_id, role);
+
+        if self
+            .pending_grants
+            .insert((account_id, role as RoleId), &ready_at)
+            .is_none()
+        {
+            self.pending_count += 1;
+        }
+
+        E::emit_role_grant_scheduled(RoleGrantScheduled {
+            role: role as RoleId,
+            account: account_id,
+            sender: caller,
+            ready_at,
+        });
+
+        Ok(())
+    }
+
+    /// cancel_grant cancels the scheduled grant of `role` to
+    /// `account_id` if `caller` holds the admin role of `role`. It
+    /// fails with `GrantNotPending` if there's no such grant or it
+    /// already matured, in which case the role has to be revoked.
+    pub fn cancel_grant<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        account_id: AccountId,
+        role: impl Role,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+        Self::check_role(role)?;
+        self.check_role_admin(caller, role)?;
+
+        if !self.is_pending(account_id, role) {
+            return Err(AccessControlError::GrantNotPending);
+        }
+
+        self.unset_role_by::<E>(caller, account_id, role);
+        Ok(())
+    }
+
+    /// execute_grant completes the scheduled grant of `role` to
+    /// `account_id` once it matured, emitting `RoleGrantExecuted` and
+    /// `RoleGranted`, adding the account to the members index and
+    /// refunding the storage of the scheduled grant. Anyone can call
+    /// it, since the role is already held by then.
+    pub fn execute_grant<E: AccessControlEvents>(
+        &mut self,
+        account_id: AccountId,
+        role: impl Role,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+
+        // grants scheduled before the role was revoked from every
+        // account are dropped instead
+        self.clear_stale(account_id, Self::page_of(role).0);
+
+        match self.scheduled_grant(account_id, role) {
+            None => return Err(AccessControlError::GrantNotPending),
+            Some(ready_at) if ready_at > ink::env::block_timestamp::<DefaultEnvironment>() => {
+                return Err(AccessControlError::GrantNotReady);
+            }
+            Some(_) => {}
+        }
+
+        let sender = Self::caller();
+        self.take_pending_grant(account_id, role);
+
+        E::emit_role_grant_executed(RoleGrantExecuted {
+            role: role as RoleId,
+            account: account_id,
+            sender,
+        });
+        self.role_changed::<E>(sender, account_id, role, true);
+
+        Ok(())
+    }
+
+    /// scheduled_grant returns the block timestamp at which the
+    /// scheduled grant of `role` to `account_id` matures, or None if
+    /// there's no grant scheduled, it was already executed or the role
+    /// was revoked from every account since
+    pub fn scheduled_grant(&self, account_id: AccountId, role: impl Role) -> Option<Timestamp> {
+        if self.pending_count == 0 {
+            return None;
+        }
+
+        let role = Self::index(role);
+        self.pending_grants
+            .get((account_id, role as RoleId))
+            .filter(|_| !self.is_stale(account_id, role))
+    }
+
+    /// is_pending returns true if `role` was scheduled to be granted
+    /// to `account_id` and it hasn't matured yet
+    pub(crate) fn is_pending(&self, account_id: AccountId, role: usize) -> bool {
+        self.scheduled_grant(account_id, role)
+            .is_some_and(|ready_at| ready_at > ink::env::block_timestamp::<DefaultEnvironment>())
+    }
+
+    /// take_pending_grant forgets the scheduled grant of `role` to
+    /// `account_id`, returning true if there was one
+    pub(crate) fn take_pending_grant(&mut self, account_id: AccountId, role: usize) -> bool {
+        if self.pending_count == 0 {
+            return false;
+        }
+
+        let taken = self
+            .pending_grants
+            .take((account_id, role as RoleId))
+            .is_some();
+        self.pending_count -= u32::from(taken);
+        taken
+    }
+
+    /// check_grant_delay fails with `DelayTooShort` if `role` can only
+    /// be granted through `schedule_grant`
+    pub(crate) fn check_grant_delay(&self, role: usize) -> Result<(), AccessControlError> {
+        if self.get_grant_delay(role) > 0 {
+            return Err(AccessControlError::DelayTooShort);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Expiry, RoleAdminChanged, RoleGrantCancelled, RoleGranted, RoleRevoked, DEFAULT_ADMIN_ROLE,
+    };
+    use ink::env::test::set_block_timestamp;
+    use std::cell::RefCell;
+
+    std::thread_local! {
+        static REVOCATIONS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Recorder keeps the kind of the revocation events emitted
+    struct Recorder;
+
+    impl AccessControlEvents for Recorder {
+        fn emit_role_granted(_: RoleGranted) {}
+
+        fn emit_role_revoked(_: RoleRevoked) {
+            REVOCATIONS.with(|emitted| emitted.borrow_mut().push("revoked"));
+        }
+
+        fn emit_role_admin_changed(_: RoleAdminChanged) {}
+
+        fn emit_role_grant_cancelled(_: RoleGrantCancelled) {
+            REVOCATIONS.with(|emitted| emitted.borrow_mut().push("cancelled"));
+        }
+    }
+
+    fn setup() -> (AccessControlData<4>, AccountId, AccountId) {
+        let mut access_control = AccessControlData::<4>::new_enumerable();
+        let (admin, account) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+
+        access_control.set_role::<()>(admin, DEFAULT_ADMIN_ROLE);
+        set_block_timestamp::<DefaultEnvironment>(1_000);
+
+        (access_control, admin, account)
+    }
+
+    #[ink::test]
+    fn expired_roles_can_be_scheduled() {
+        let (mut access_control, admin, account) = setup();
+        let role = 1;
+
+        access_control.set_role_until::<()>(account, role, Expiry::Timestamp(1_000));
+        assert!(!access_control.has_role(account, role));
+
+        assert_eq!(access_control.schedule_grant::<()>(admin, account, role, 500), Ok(()));
+        assert_eq!(access_control.scheduled_grant(account, role), Some(1_500));
+        assert_eq!(access_control.role_expiry(account, role), None);
+        assert_eq!(access_control.get_role_member_count(role), 0);
+
+        set_block_timestamp::<DefaultEnvironment>(1_500);
+        assert!(access_control.has_role(account, role));
+        assert_eq!(access_control.execute_grant::<()>(account, role), Ok(()));
+        assert_eq!(access_control.role_members(role, 0, 10), [account]);
+    }
+
+    #[ink::test]
+    fn scheduled_grants_mature() {
+        let (mut access_control, admin, account) = setup();
+        let role = 1;
+
+        assert_eq!(access_control.schedule_grant::<()>(admin, account, role, 500), Ok(()));
+        assert_eq!(access_control.scheduled_grant(account, role), Some(1_500));
+
+        assert!(!access_control.has_role(account, role));
+        assert!(!access_control.has_any_role(account, &[role]));
+        assert!(access_control.roles_of(account).is_empty());
+        assert_eq!(
+            access_control.execute_grant::<()>(account, role),
+            Err(AccessControlError::GrantNotReady)
+        );
+
+        set_block_timestamp::<DefaultEnvironment>(1_500);
+        assert!(access_control.has_role(account, role));
+        assert_eq!(access_control.get_role_member_count(role), 0);
+
+        assert_eq!(access_control.execute_grant::<()>(account, role), Ok(()));
+        assert!(access_control.has_role(account, role));
+        assert_eq!(access_control.scheduled_grant(account, role), None);
+        assert_eq!(access_control.get_role_member_count(role), 1);
+        assert_eq!(access_control.pending_count, 0);
+
+        assert_eq!(
+            access_control.execute_grant::<()>(account, role),
+            Err(AccessControlError::GrantNotPending)
+        );
+    }
+
+    #[ink::test]
+    fn scheduled_grants_can_be_cancelled() {
+        let (mut access_control, admin, account) = setup();
+        let role = 1;
+
+        access_control.schedule_grant::<()>(admin, account, role, 500).unwrap();
+
+        // only admins can cancel
+        assert_eq!(
+            access_control.cancel_grant::<()>(account, account, role),
+            Err(AccessControlError::MissingRole {
+                account,
+                role: DEFAULT_ADMIN_ROLE as RoleId,
+            })
+        );
+
+        assert_eq!(access_control.cancel_grant::<()>(admin, account, role), Ok(()));
+        assert_eq!(access_control.scheduled_grant(account, role), None);
+        assert!(!access_control.roles_per_account.contains((account, 0)));
+
+        set_block_timestamp::<DefaultEnvironment>(1_500);
+        assert!(!access_control.has_role(account, role));
+
+        // matured grants can't be cancelled anymore
+        access_control.schedule_grant::<()>(admin, account, role, 0).unwrap();
+        assert_eq!(
+            access_control.cancel_grant::<()>(admin, account, role),
+            Err(AccessControlError::GrantNotPending)
+        );
+    }
+
+    #[ink::test]
+    fn grant_delays_are_enforced() {
+        let (mut access_control, admin, account) = setup();
+        let (role, other) = (1, 2);
+
+        access_control.set_grant_delay(role, 500);
+        assert_eq!(access_control.get_grant_delay(role), 500);
+        assert_eq!(access_control.get_grant_delay(other), 0);
+
+        assert_eq!(
+            access_control.schedule_grant::<()>(admin, account, role, 499),
+            Err(AccessControlError::DelayTooShort)
+        );
+        assert_eq!(
+            access_control.grant_role::<()>(admin, account, role),
+            Err(AccessControlError::DelayTooShort)
+        );
+        assert_eq!(
+            access_control.grant_roles::<()>(admin, &[(account, other), (account, role)]),
+            Err(AccessControlError::DelayTooShort)
+        );
+        assert!(!access_control.has_role(account, other));
+
+        assert_eq!(access_control.schedule_grant::<()>(admin, account, role, 500), Ok(()));
+        assert_eq!(access_control.grant_role::<()>(admin, account, other), Ok(()));
+
+        access_control.set_grant_delay(role, 0);
+        assert_eq!(access_control.delayed_roles, 0);
+        assert_eq!(access_control.grant_role::<()>(admin, account, role), Ok(()));
+    }
+
+    #[ink::test]
+    fn setting_or_revoking_a_scheduled_role_ends_the_schedule() {
+        let (mut access_control, admin, account) = setup();
+        let role = 1;
+
+        access_control.schedule_grant::<()>(admin, account, role, 500).unwrap();
+        access_control.set_role::<()>(account, role);
+
+        assert!(access_control.has_role(account, role));
+        assert_eq!(access_control.scheduled_grant(account, role), None);
+        assert_eq!(access_control.get_role_member_count(role), 1);
+
+        access_control.schedule_grant::<()>(admin, admin, role, 500).unwrap();
+        access_control.unset_role::<()>(admin, role);
+
+        assert_eq!(access_control.scheduled_grant(admin, role), None);
+        assert_eq!(access_control.pending_count, 0);
+    }
+
+    #[ink::test]
+    fn matured_grants_are_revoked_instead_of_cancelled() {
+        let (mut access_control, admin, account) = setup();
+
+        access_control.schedule_grant::<()>(admin, account, 1, 500).unwrap();
+        access_control.schedule_grant::<()>(admin, account, 2, 500).unwrap();
+        access_control.revoke_role::<Recorder>(admin, account, 1).unwrap();
+
+        set_block_timestamp::<DefaultEnvironment>(1_500);
+        access_control.revoke_role::<Recorder>(admin, account, 2).unwrap();
+
+        assert_eq!(REVOCATIONS.with(|emitted| emitted.take()), ["cancelled", "revoked"]);
+        assert_eq!(access_control.pending_count, 0);
+        assert!(!access_control.has_role(account, 2));
+    }
+}
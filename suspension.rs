@@ -0,0 +1,233 @@
+use ink::primitives::AccountId;
+
+use crate::{
+    AccessControlData, AccessControlError, AccessControlEvents, BitMap, Role, RoleId, RoleMask,
+    RoleResumed, RoleSuspended,
+};
+
+impl<const N: usize, const PAGES: usize> AccessControlData<N, PAGES> {
+    /// suspend_role suspends `role` if `caller` holds the guardian
+    /// role, so that `has_role` and the rest of queries treat it as not
+    /// held by anyone, emitting `RoleSuspended` through `E` if it wasn't
+    /// suspended yet. The accounts keep it in their bitmaps, and in the
+    /// members index, so resuming it with `resume_role` restores the
+    /// same holders.
+    ///
+    /// Accounts can't administer other roles with a suspended role
+    /// either, so suspending `DEFAULT_ADMIN_ROLE` leaves the contract
+    /// without admins, default admin rules included, until it's
+    /// resumed. The guardian role itself can't be suspended, since
+    /// nobody could resume it, and fails with `GuardianSuspension`.
+    pub fn suspend_role<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        role: impl Role,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+        Self::check_role(role)?;
+        self.ensure_role(caller, self.get_guardian_role())?;
+
+        if role == self.get_guardian_role() {
+            return Err(AccessControlError::GuardianSuspension);
+        }
+
+        let (page, bit) = Self::page_of(role);
+        let mut suspended = self.suspended_roles.get(page).unwrap_or_default();
+
+        if suspended.has_bit_set(bit) {
+            return Ok(());
+        }
+
+        suspended.set_bit(bit);
+        self.suspended_roles.insert(page, &suspended);
+        self.suspended_count += 1;
+
+        E::emit_role_suspended(RoleSuspended {
+            role:   role as RoleId,
+            sender: caller,
+        });
+
+        Ok(())
+    }
+
+    /// resume_role resumes the suspended `role` if `caller` holds the
+    /// guardian role, emitting `RoleResumed` through `E` if it was
+    /// suspended
+    pub fn resume_role<E: AccessControlEvents>(
+        &mut self,
+        caller: AccountId,
+        role: impl Role,
+    ) -> Result<(), AccessControlError> {
+        let role = Self::index(role);
+        Self::check_role(role)?;
+        self.ensure_role(caller, self.get_guardian_role())?;
+
+        let (page, bit) = Self::page_of(role);
+        let mut suspended = match self.suspended_roles.get(page) {
+            Some(suspended) if suspended.has_bit_set(bit) => suspended,
+            _ => return Ok(()),
+        };
+
+        suspended.clear_bit(bit);
+        if suspended.is_empty() {
+            self.suspended_roles.remove(page);
+        } else {
+            self.suspended_roles.insert(page, &suspended);
+        }
+        self.suspended_count -= 1;
+
+        E::emit_role_resumed(RoleResumed {
+            role:   role as RoleId,
+            sender: caller,
+        });
+
+        Ok(())
+    }
+
+    /// is_role_suspended returns true if `role` is suspended
+    pub fn is_role_suspended(&self, role: impl Role) -> bool {
+        if self.suspended_count == 0 {
+            return false;
+        }
+
+        let role = Self::index(role);
+        if Self::check_role(role).is_err() {
+            return false;
+        }
+
+        let (page, bit) = Self::page_of(role);
+        self.suspended_roles
+            .get(page)
+            .is_some_and(|suspended| suspended.has_bit_set(bit))
+    }
+
+    /// suspended_roles returns every suspended role
+    pub fn suspended_roles(&self) -> RoleMask<N, PAGES> {
+        let mut mask = RoleMask::new();
+
+        if self.suspended_count > 0 {
+            for (page, roles) in mask.pages.iter_mut().enumerate() {
+                *roles = self.suspended_roles.get(page as u32).unwrap_or_default();
+            }
+        }
+
+        mask
+    }
+
+    /// without_suspended returns `roles`, a page of the roles of an
+    /// account, without the roles suspended in that page
+    pub(crate) fn without_suspended(&self, page: u32, roles: BitMap<N>) -> BitMap<N> {
+        if self.suspended_count == 0 {
+            return roles;
+        }
+
+        match self.suspended_roles.get(page) {
+            Some(suspended) => roles.difference(&suspended),
+            None => roles,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DEFAULT_ADMIN_ROLE;
+    use ink::env::{test::set_block_timestamp, DefaultEnvironment};
+
+    const GUARDIAN: usize = 2;
+
+    #[ink::test]
+    fn suspended_roles_arent_held() {
+        let mut access_control = AccessControlData::<1, 2>::new_enumerable();
+        let (alice, bob) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+        let guardian = AccountId::from([3u8; 32]);
+        let (operator, other) = (9, 1);
+
+        access_control.set_guardian_role(GUARDIAN);
+        access_control.set_role::<()>(guardian, GUARDIAN);
+        access_control.set_role_batch::<()>(&[(alice, operator), (bob, operator), (alice, other)]);
+        assert_eq!(access_control.suspend_role::<()>(guardian, operator), Ok(()));
+
+        assert!(access_control.is_role_suspended(operator));
+        assert!(!access_control.is_role_suspended(other));
+        assert_eq!(access_control.suspended_roles(), RoleMask::of(&[operator]));
+
+        assert!(!access_control.has_role(alice, operator));
+        assert!(!access_control.has_role(bob, operator));
+        assert!(access_control.has_role(alice, other));
+        assert!(!access_control.has_any_role(bob, &[operator, other]));
+        assert!(!access_control.has_all_roles(alice, &[operator, other]));
+        assert_eq!(access_control.roles_of(alice), [other]);
+        assert_eq!(
+            access_control.ensure_role(bob, operator),
+            Err(AccessControlError::MissingRole {
+                account: bob,
+                role:    operator as RoleId,
+            })
+        );
+
+        // the holders are restored on resumption
+        assert_eq!(access_control.resume_role::<()>(guardian, operator), Ok(()));
+        assert!(access_control.has_role(alice, operator));
+        assert!(access_control.has_role(bob, operator));
+        assert_eq!(access_control.roles_of(alice), [other, operator]);
+        assert_eq!(access_control.get_role_member_count(operator), 2);
+
+        assert_eq!(access_control.suspended_count, 0);
+        assert!(!access_control.suspended_roles.contains(1));
+    }
+
+    #[ink::test]
+    fn only_guardians_can_suspend() {
+        let mut access_control = AccessControlData::<4>::new();
+        let (guardian, other) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+        let missing_role = Err(AccessControlError::MissingRole {
+            account: other,
+            role:    DEFAULT_ADMIN_ROLE as RoleId,
+        });
+
+        access_control.set_role::<()>(guardian, DEFAULT_ADMIN_ROLE);
+        assert_eq!(access_control.suspend_role::<()>(other, 1), missing_role);
+
+        access_control.suspend_role::<()>(guardian, 1).unwrap();
+        assert_eq!(access_control.resume_role::<()>(other, 1), missing_role);
+        assert!(access_control.is_role_suspended(1));
+
+        // nobody could resume the guardian role
+        assert_eq!(
+            access_control.suspend_role::<()>(guardian, DEFAULT_ADMIN_ROLE),
+            Err(AccessControlError::GuardianSuspension)
+        );
+        assert_eq!(
+            access_control.suspend_role::<()>(guardian, 32),
+            Err(AccessControlError::RoleOutOfRange)
+        );
+    }
+
+    #[ink::test]
+    fn suspended_admin_roles_cant_administer() {
+        let mut access_control = AccessControlData::<4>::new();
+        let (admin, account) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+        let guardian = AccountId::from([3u8; 32]);
+
+        set_block_timestamp::<DefaultEnvironment>(1_000);
+        access_control.enable_default_admin_rules::<()>(admin, 0);
+        access_control.set_guardian_role(GUARDIAN);
+        access_control.set_role::<()>(guardian, GUARDIAN);
+        access_control.suspend_role::<()>(guardian, DEFAULT_ADMIN_ROLE).unwrap();
+
+        let missing_role = Err(AccessControlError::MissingRole {
+            account: admin,
+            role:    DEFAULT_ADMIN_ROLE as RoleId,
+        });
+        assert_eq!(access_control.grant_role::<()>(admin, account, 1), missing_role);
+        assert_eq!(
+            access_control.begin_default_admin_transfer::<()>(admin, account),
+            missing_role
+        );
+
+        access_control.resume_role::<()>(guardian, DEFAULT_ADMIN_ROLE).unwrap();
+        assert_eq!(access_control.grant_role::<()>(admin, account, 1), Ok(()));
+        assert_eq!(access_control.begin_default_admin_transfer::<()>(admin, account), Ok(()));
+    }
+}
@@ -0,0 +1,81 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[access_control::access_control]
+#[ink::contract]
+mod hashed {
+    use access_control::{
+        only_role, role_id, AccessControlError, HashedAccessControlData, RoleId,
+        DEFAULT_ADMIN_ROLE_ID,
+    };
+
+    #[ink(storage)]
+    pub struct Hashed {
+        access_control: HashedAccessControlData,
+        value:          bool,
+    }
+
+    impl Hashed {
+        pub const FLIPPER: RoleId = role_id("FLIPPER");
+
+        #[ink(constructor)]
+        pub fn new(value: bool) -> Self {
+            let caller = Self::env().caller();
+            let mut access_control = HashedAccessControlData::new();
+
+            access_control.set_role::<Self>(caller, DEFAULT_ADMIN_ROLE_ID);
+
+            Self {
+                value,
+                access_control,
+            }
+        }
+
+        #[ink(message)]
+        #[only_role(Self::FLIPPER)]
+        pub fn privileged_flip(&mut self) -> Result<(), AccessControlError> {
+            self.value = !self.value;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get(&self) -> bool {
+            self.value
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use access_control::AccessControl;
+
+        #[ink::test]
+        fn role_ids_are_derived_from_names() {
+            assert_eq!(Hashed::FLIPPER, ink::selector_id!("FLIPPER"));
+        }
+
+        #[ink::test]
+        fn access_control_messages_work() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = Hashed::new(false);
+
+            assert_eq!(contract.get_role_admin(Hashed::FLIPPER), DEFAULT_ADMIN_ROLE_ID);
+
+            assert_eq!(
+                contract.privileged_flip(),
+                Err(AccessControlError::MissingRole {
+                    account: accounts.alice,
+                    role:    Hashed::FLIPPER,
+                })
+            );
+
+            assert_eq!(contract.grant_role(Hashed::FLIPPER, accounts.alice), Ok(()));
+            assert!(contract.has_role(Hashed::FLIPPER, accounts.alice));
+
+            assert_eq!(contract.privileged_flip(), Ok(()));
+            assert!(contract.get());
+
+            assert_eq!(contract.renounce_role(Hashed::FLIPPER, accounts.alice), Ok(()));
+            assert!(!contract.has_role(Hashed::FLIPPER, accounts.alice));
+        }
+    }
+}
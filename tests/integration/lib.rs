@@ -1,28 +1,36 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+#[access_control::access_control]
 #[ink::contract]
 mod integration {
-    use access_control::AccessControlData;
+    use access_control::{
+        only_role, when_not_paused, AccessControlData, AccessControlError, PausableData,
+        DEFAULT_ADMIN_ROLE,
+    };
 
     #[ink(storage)]
     pub struct Integration {
         access_control: AccessControlData<4>,
+        pausable:       PausableData,
         value:          bool,
     }
 
     impl Integration {
-        const ROLE_1: usize = 0;
+        const ROLE_1: usize = 1;
+        const ROLE_2: usize = 2;
 
         #[ink(constructor)]
         pub fn new(value: bool) -> Self {
             let caller = Self::env().caller();
             let mut access_control = AccessControlData::<4>::new();
 
-            access_control.set_role(caller, Self::ROLE_1);
+            access_control.set_role::<Self>(caller, DEFAULT_ADMIN_ROLE);
+            access_control.set_role::<Self>(caller, Self::ROLE_1);
 
             Self {
                 value,
                 access_control,
+                pausable: PausableData::new(Self::ROLE_1),
             }
         }
 
@@ -32,13 +40,29 @@ mod integration {
         }
 
         #[ink(message)]
-        pub fn privileged_flip(&mut self) -> Result<(), ()> {
-            let caller = self.env().caller();
+        #[only_role(Self::ROLE_1)]
+        pub fn privileged_flip(&mut self) -> Result<(), AccessControlError> {
+            self.value = !self.value;
+            Ok(())
+        }
 
-            if !self.access_control.has_role(caller, Self::ROLE_1) {
-                return Err(());
-            }
+        #[ink(message)]
+        #[only_role(any_of(Self::ROLE_1, Self::ROLE_2))]
+        pub fn set_true(&mut self) -> Result<(), AccessControlError> {
+            self.value = true;
+            Ok(())
+        }
+
+        #[ink(message)]
+        #[only_role(all_of(Self::ROLE_1, Self::ROLE_2))]
+        pub fn set_false(&mut self) -> Result<(), AccessControlError> {
+            self.value = false;
+            Ok(())
+        }
 
+        #[ink(message)]
+        #[when_not_paused]
+        pub fn pausable_flip(&mut self) -> Result<(), AccessControlError> {
             self.value = !self.value;
             Ok(())
         }
@@ -52,31 +76,56 @@ mod integration {
     #[cfg(test)]
     mod tests {
         use super::*;
+        use access_control::{
+            AccessControl, AccessControlBatch, AccessControlIntrospection, Pausable, RoleId,
+        };
+
+        type Event = <Integration as ::ink::reflect::ContractEventBase>::Type;
 
         #[ink::test]
         fn flip_works() {
             let mut contract = Integration::new(false);
-            assert_eq!(contract.get(), false);
+            assert!(!contract.get());
 
             contract.flip();
-            assert_eq!(contract.get(), true);
+            assert!(contract.get());
+        }
+
+        #[ink::test]
+        fn new_emits_role_granted() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let _contract = Integration::new(false);
+
+            let events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(events.len(), 2);
+
+            for (event, role) in events.iter().zip([DEFAULT_ADMIN_ROLE, Integration::ROLE_1]) {
+                let decoded = <Event as scale::Decode>::decode(&mut &event.data[..]).unwrap();
+                let Event::RoleGranted(event) = decoded else {
+                    panic!("expected RoleGranted");
+                };
+
+                assert_eq!(event.role, role as RoleId);
+                assert_eq!(event.account, accounts.alice);
+                assert_eq!(event.sender, accounts.alice);
+            }
         }
 
         #[ink::test]
         fn privileged_flip_on_granted_account_works() {
             let mut contract = Integration::new(false);
-            assert_eq!(contract.get(), false);
+            assert!(!contract.get());
 
             let res = contract.privileged_flip();
 
             assert!(res.is_ok());
-            assert_eq!(contract.get(), true);
+            assert!(contract.get());
         }
 
         #[ink::test]
         fn privileged_flip_on_non_granted_account_fails() {
             let mut contract = Integration::new(false);
-            assert_eq!(contract.get(), false);
+            assert!(!contract.get());
 
             // set the call to the contract to be done by the account bob,
             // who has no roles granted
@@ -88,8 +137,162 @@ mod integration {
 
             let res = contract.privileged_flip();
 
-            assert!(res.is_err());
-            assert_eq!(contract.get(), false);
+            assert_eq!(
+                res,
+                Err(AccessControlError::MissingRole {
+                    account: accounts.bob,
+                    role:    Integration::ROLE_1 as u32,
+                })
+            );
+            assert!(!contract.get());
+        }
+
+        #[ink::test]
+        fn only_role_any_of_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = Integration::new(false);
+
+            // alice holds ROLE_1 only
+            assert_eq!(contract.set_true(), Ok(()));
+            assert!(contract.get());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.set_true(),
+                Err(AccessControlError::MissingRole {
+                    account: accounts.bob,
+                    role:    Integration::ROLE_1 as RoleId,
+                })
+            );
+        }
+
+        #[ink::test]
+        fn only_role_all_of_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = Integration::new(true);
+
+            // alice holds ROLE_1 but not ROLE_2
+            assert_eq!(
+                contract.set_false(),
+                Err(AccessControlError::MissingRole {
+                    account: accounts.alice,
+                    role:    Integration::ROLE_2 as RoleId,
+                })
+            );
+            assert!(contract.get());
+
+            contract
+                .access_control
+                .set_role::<()>(accounts.alice, Integration::ROLE_2);
+
+            assert_eq!(contract.set_false(), Ok(()));
+            assert!(!contract.get());
+        }
+
+        #[ink::test]
+        fn access_control_messages_work() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = Integration::new(false);
+            let role = Integration::ROLE_2 as RoleId;
+
+            assert_eq!(contract.get_role_admin(role), DEFAULT_ADMIN_ROLE as RoleId);
+
+            // alice is the default admin
+            assert_eq!(contract.grant_role(role, accounts.bob), Ok(()));
+            assert!(contract.has_role(role, accounts.bob));
+
+            assert_eq!(contract.revoke_role(role, accounts.bob), Ok(()));
+            assert!(!contract.has_role(role, accounts.bob));
+
+            assert_eq!(contract.grant_role(role, accounts.bob), Ok(()));
+
+            // bob can't grant, but can renounce
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(
+                contract.grant_role(role, accounts.charlie),
+                Err(AccessControlError::MissingRole {
+                    account: accounts.bob,
+                    role:    DEFAULT_ADMIN_ROLE as RoleId,
+                })
+            );
+            assert_eq!(
+                contract.renounce_role(role, accounts.alice),
+                Err(AccessControlError::BadConfirmation)
+            );
+            assert_eq!(contract.renounce_role(role, accounts.bob), Ok(()));
+            assert!(!contract.has_role(role, accounts.bob));
+
+            // 2 grants in the constructor, 2 grants and 2 revocations
+            assert_eq!(ink::env::test::recorded_events().count(), 6);
+        }
+
+        #[ink::test]
+        fn access_control_batch_messages_work() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = Integration::new(false);
+            let (role_1, role_2) = (Integration::ROLE_1 as RoleId, Integration::ROLE_2 as RoleId);
+
+            assert_eq!(
+                contract.grant_roles(vec![(role_1, accounts.bob), (role_2, accounts.charlie)]),
+                Ok(())
+            );
+            assert!(contract.has_role(role_1, accounts.bob));
+            assert!(contract.has_role(role_2, accounts.charlie));
+
+            // roles out of range make the whole batch fail
+            assert_eq!(
+                contract.revoke_roles(vec![(role_1, accounts.bob), (32, accounts.bob)]),
+                Err(AccessControlError::RoleOutOfRange)
+            );
+            assert!(contract.has_role(role_1, accounts.bob));
+
+            assert_eq!(contract.replace_roles(accounts.bob, vec![role_2]), Ok(()));
+            assert!(!contract.has_role(role_1, accounts.bob));
+            assert!(contract.has_role(role_2, accounts.bob));
+
+            assert_eq!(
+                contract.revoke_roles(vec![(role_2, accounts.bob), (role_2, accounts.charlie)]),
+                Ok(())
+            );
+            assert!(!contract.has_role(role_2, accounts.bob));
+            assert!(!contract.has_role(role_2, accounts.charlie));
+
+            assert_eq!(contract.roles_of(accounts.alice), [0, 1]);
+            assert!(contract.roles_of(accounts.bob).is_empty());
+
+            // 2 grants in the constructor, 2 grants, 1 grant and 1
+            // revocation replacing, and 2 revocations
+            assert_eq!(ink::env::test::recorded_events().count(), 8);
+        }
+
+        #[ink::test]
+        fn pausable_messages_work() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = Integration::new(false);
+
+            // alice holds the pauser role, ROLE_1
+            assert_eq!(contract.pause(), Ok(()));
+            assert!(contract.paused());
+            assert_eq!(contract.pausable_flip(), Err(AccessControlError::EnforcedPause));
+            assert!(!contract.get());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.unpause(),
+                Err(AccessControlError::MissingRole {
+                    account: accounts.bob,
+                    role:    Integration::ROLE_1 as RoleId,
+                })
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.unpause(), Ok(()));
+            assert_eq!(contract.pausable_flip(), Ok(()));
+            assert!(contract.get());
+
+            // 2 grants in the constructor, a pause and an unpause
+            assert_eq!(ink::env::test::recorded_events().count(), 4);
         }
     }
 }
@@ -0,0 +1,222 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Layout keeps the roles of its accounts in both the current
+/// `BitMap`, a fixed-size array, and the length-prefixed vector it
+/// used to be, so that the cost of the same messages can be compared
+/// on a node.
+#[access_control::access_control]
+#[ink::contract]
+mod layout {
+    use access_control::{
+        AccessControlData, AccessControlError, AccessControlEvents, RoleId, DEFAULT_ADMIN_ROLE,
+    };
+    use ink::{prelude::vec, prelude::vec::Vec, storage::Mapping};
+
+    const N: usize = 4;
+
+    /// LegacyBitMap is `BitMap` as it was stored before it became a
+    /// fixed-size array, a vector of `N` bytes.
+    #[derive(scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct LegacyBitMap(Vec<u8>);
+
+    impl LegacyBitMap {
+        fn new() -> Self {
+            LegacyBitMap(vec![0u8; N])
+        }
+
+        fn set_bit(&mut self, pos: usize) {
+            self.0[pos / 8] |= 1u8 << (pos % 8);
+        }
+
+        fn has_bit_set(&self, pos: usize) -> bool {
+            (self.0[pos / 8] & (1 << (pos % 8))) > 0
+        }
+    }
+
+    #[ink(storage)]
+    pub struct Layout {
+        access_control: AccessControlData<N>,
+        legacy_roles:   Mapping<(AccountId, u32), LegacyBitMap>,
+    }
+
+    impl Layout {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            let caller = Self::env().caller();
+            let mut access_control = AccessControlData::new();
+            let mut legacy_roles = Mapping::new();
+            let mut roles = LegacyBitMap::new();
+
+            access_control.set_role::<Self>(caller, DEFAULT_ADMIN_ROLE);
+            roles.set_bit(DEFAULT_ADMIN_ROLE);
+            legacy_roles.insert((caller, 0), &roles);
+
+            Self {
+                access_control,
+                legacy_roles,
+            }
+        }
+
+        /// legacy_has_role is `has_role` over the vector layout. Like
+        /// `has_role` it checks the range of the role and reads a single
+        /// page.
+        #[ink(message)]
+        pub fn legacy_has_role(&self, role: RoleId, account: AccountId) -> bool {
+            if role as usize >= N * 8 {
+                return false;
+            }
+
+            self.legacy_roles
+                .get((account, 0))
+                .unwrap_or_else(LegacyBitMap::new)
+                .has_bit_set(role as usize)
+        }
+
+        /// legacy_grant_role is `grant_role` over the vector layout. Like
+        /// `grant_role` it reads the admin of the role, the page of the
+        /// caller and the page of the account, writes the latter and
+        /// emits `RoleGranted`.
+        #[ink(message)]
+        pub fn legacy_grant_role(
+            &mut self,
+            role: RoleId,
+            account: AccountId,
+        ) -> Result<(), AccessControlError> {
+            if role as usize >= N * 8 {
+                return Err(AccessControlError::RoleOutOfRange);
+            }
+
+            let caller = self.env().caller();
+            let admin_role = self.access_control.get_role_admin(role as usize) as RoleId;
+
+            if !self.legacy_has_role(admin_role, caller) {
+                return Err(AccessControlError::MissingRole {
+                    account: caller,
+                    role:    admin_role,
+                });
+            }
+
+            let mut roles = self
+                .legacy_roles
+                .get((account, 0))
+                .unwrap_or_else(LegacyBitMap::new);
+            if roles.has_bit_set(role as usize) {
+                return Ok(());
+            }
+
+            roles.set_bit(role as usize);
+            self.legacy_roles.insert((account, 0), &roles);
+            Self::emit_role_granted(access_control::RoleGranted {
+                role,
+                account,
+                sender: caller,
+            });
+
+            Ok(())
+        }
+    }
+
+    impl Default for Layout {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use access_control::AccessControl;
+
+        #[ink::test]
+        fn both_layouts_hold_the_same_roles() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = Layout::new();
+
+            assert_eq!(contract.grant_role(3, accounts.bob), Ok(()));
+            assert_eq!(contract.legacy_grant_role(3, accounts.bob), Ok(()));
+
+            for role in 0..(N * 8) as RoleId {
+                assert_eq!(
+                    contract.has_role(role, accounts.bob),
+                    contract.legacy_has_role(role, accounts.bob)
+                );
+            }
+        }
+    }
+
+    /// Compares the weight consumed by `has_role` and `grant_role` over
+    /// the array layout with the same messages over the vector layout,
+    /// which do the same reads, writes and events. The array layout has
+    /// to be cheaper in both `ref_time` and `proof_size`. Needs a node,
+    /// run with `cargo test --features e2e-tests`.
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use access_control::AccessControl;
+        use ink_e2e::build_message;
+
+        type E2EResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+        const ROLE: RoleId = 3;
+
+        #[ink_e2e::test]
+        async fn bitmap_layouts_gas(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let contract = client
+                .instantiate("layout", &ink_e2e::alice(), LayoutRef::new(), 0, None)
+                .await
+                .expect("instantiate failed")
+                .account_id;
+            let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+
+            let grant = build_message::<LayoutRef>(contract.clone())
+                .call(|layout| layout.grant_role(ROLE, bob));
+            let grant = client
+                .call(&ink_e2e::alice(), grant, 0, None)
+                .await
+                .expect("grant_role failed")
+                .dry_run
+                .exec_result
+                .gas_consumed;
+
+            let legacy_grant = build_message::<LayoutRef>(contract.clone())
+                .call(|layout| layout.legacy_grant_role(ROLE, bob));
+            let legacy_grant = client
+                .call(&ink_e2e::alice(), legacy_grant, 0, None)
+                .await
+                .expect("legacy_grant_role failed")
+                .dry_run
+                .exec_result
+                .gas_consumed;
+
+            let has_role = build_message::<LayoutRef>(contract.clone())
+                .call(|layout| layout.has_role(ROLE, bob));
+            let has_role = client.call_dry_run(&ink_e2e::alice(), &has_role, 0, None).await;
+            let (has_role, held) = (has_role.exec_result.gas_consumed, has_role.return_value());
+            assert!(held);
+
+            let legacy_has_role = build_message::<LayoutRef>(contract.clone())
+                .call(|layout| layout.legacy_has_role(ROLE, bob));
+            let legacy_has_role = client
+                .call_dry_run(&ink_e2e::alice(), &legacy_has_role, 0, None)
+                .await;
+            let (legacy_has_role, held) = (
+                legacy_has_role.exec_result.gas_consumed,
+                legacy_has_role.return_value(),
+            );
+            assert!(held);
+
+            // the bitmaps are one byte shorter and decoded without
+            // allocating
+            assert!(grant.ref_time() < legacy_grant.ref_time());
+            assert!(grant.proof_size() < legacy_grant.proof_size());
+            assert!(has_role.ref_time() < legacy_has_role.ref_time());
+            assert!(has_role.proof_size() < legacy_has_role.proof_size());
+
+            Ok(())
+        }
+    }
+}
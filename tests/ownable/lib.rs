@@ -0,0 +1,98 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[access_control::access_control]
+#[ink::contract]
+mod ownable {
+    use access_control::{only_owner, AccessControlError, OwnableData};
+
+    #[ink(storage)]
+    pub struct Ownable {
+        ownable: OwnableData,
+        value:   bool,
+    }
+
+    impl Ownable {
+        #[ink(constructor)]
+        pub fn new(value: bool) -> Self {
+            let mut ownable = OwnableData::new();
+            ownable.set_owner::<Self>(Self::env().caller());
+
+            Self { ownable, value }
+        }
+
+        #[ink(message)]
+        #[only_owner]
+        pub fn privileged_flip(&mut self) -> Result<(), AccessControlError> {
+            self.value = !self.value;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get(&self) -> bool {
+            self.value
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use access_control::Ownable as _;
+
+        type Event = <Ownable as ::ink::reflect::ContractEventBase>::Type;
+
+        #[ink::test]
+        fn new_emits_ownership_transferred() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = Ownable::new(false);
+
+            assert_eq!(contract.owner(), Some(accounts.alice));
+
+            let events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(events.len(), 1);
+
+            let decoded = <Event as scale::Decode>::decode(&mut &events[0].data[..]).unwrap();
+            let Event::OwnershipTransferred(event) = decoded else {
+                panic!("expected OwnershipTransferred");
+            };
+
+            assert_eq!(event.previous_owner, None);
+            assert_eq!(event.new_owner, Some(accounts.alice));
+        }
+
+        #[ink::test]
+        fn ownable_messages_work() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = Ownable::new(false);
+
+            assert_eq!(contract.privileged_flip(), Ok(()));
+            assert!(contract.get());
+
+            assert_eq!(contract.transfer_ownership(accounts.bob), Ok(()));
+            assert_eq!(contract.pending_owner(), Some(accounts.bob));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.privileged_flip(),
+                Err(AccessControlError::NotOwner {
+                    account: accounts.bob,
+                })
+            );
+
+            assert_eq!(contract.accept_ownership(), Ok(()));
+            assert_eq!(contract.owner(), Some(accounts.bob));
+            assert_eq!(contract.privileged_flip(), Ok(()));
+
+            assert_eq!(contract.renounce_ownership(), Ok(()));
+            assert_eq!(contract.owner(), None);
+            assert_eq!(
+                contract.privileged_flip(),
+                Err(AccessControlError::NotOwner {
+                    account: accounts.bob,
+                })
+            );
+
+            // the constructor's, started, transferred and renounced
+            assert_eq!(ink::env::test::recorded_events().count(), 4);
+        }
+    }
+}